@@ -1,15 +1,19 @@
 use std::fmt::{Debug, Display};
 
+use aes::cipher::{KeyIvInit, StreamCipher};
 #[allow(deprecated)]
 // This is the only source file in production code where it's allowed to create
 // `DangerousSecretKeyStr`.
 use edr_eth::signature::{secret_key_from_str, DangerousSecretKeyStr};
-use napi::{bindgen_prelude::BigInt, JsString, Status};
+use napi::{bindgen_prelude::BigInt, Env, JsString, Status};
 use napi_derive::napi;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 
 use crate::cast::TryCast;
 
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
 /// An account that needs to be created during the genesis block.
 #[napi(object)]
 pub struct GenesisAccount {
@@ -44,3 +48,152 @@ impl TryFrom<GenesisAccount> for edr_provider::AccountConfig {
         })
     }
 }
+
+/// A Web3 Secret Storage (v3) keystore, as produced by e.g. geth's `account
+/// import`/`personal_newAccount`.
+#[derive(Deserialize)]
+struct Keystore {
+    crypto: KeystoreCrypto,
+}
+
+#[derive(Deserialize)]
+struct KeystoreCrypto {
+    ciphertext: String,
+    cipherparams: KeystoreCipherParams,
+    kdf: String,
+    kdfparams: KeystoreKdfParams,
+    mac: String,
+}
+
+#[derive(Deserialize)]
+struct KeystoreCipherParams {
+    iv: String,
+}
+
+/// The union of the `scrypt` and `pbkdf2` KDF parameter shapes. Unused fields
+/// for the selected `kdf` are left as their default.
+#[derive(Default, Deserialize)]
+struct KeystoreKdfParams {
+    #[serde(default)]
+    n: u32,
+    #[serde(default)]
+    r: u32,
+    #[serde(default)]
+    p: u32,
+    #[serde(default)]
+    c: u32,
+    dklen: u32,
+    salt: String,
+}
+
+/// An error that occurred while importing a Web3 Secret Storage keystore.
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    /// The keystore JSON is malformed or uses an unsupported KDF.
+    #[error("Malformed keystore: {0}")]
+    Malformed(String),
+    /// The derived MAC doesn't match the MAC stored in the keystore, which
+    /// means the passphrase is incorrect or the file is corrupted.
+    #[error("MAC mismatch: incorrect passphrase or corrupted keystore")]
+    MacMismatch,
+}
+
+impl From<KeystoreError> for napi::Error {
+    fn from(value: KeystoreError) -> Self {
+        napi::Error::new(Status::InvalidArg, value.to_string())
+    }
+}
+
+fn decrypt_keystore(keystore_json: &str, passphrase: &str) -> Result<[u8; 32], KeystoreError> {
+    let keystore: Keystore = serde_json::from_str(keystore_json)
+        .map_err(|error| KeystoreError::Malformed(error.to_string()))?;
+
+    let ciphertext = hex::decode(&keystore.crypto.ciphertext)
+        .map_err(|error| KeystoreError::Malformed(error.to_string()))?;
+    let iv = hex::decode(&keystore.crypto.cipherparams.iv)
+        .map_err(|error| KeystoreError::Malformed(error.to_string()))?;
+    let salt = hex::decode(&keystore.crypto.kdfparams.salt)
+        .map_err(|error| KeystoreError::Malformed(error.to_string()))?;
+    let mac = hex::decode(&keystore.crypto.mac)
+        .map_err(|error| KeystoreError::Malformed(error.to_string()))?;
+
+    let dklen = keystore.crypto.kdfparams.dklen as usize;
+    let mut derived_key = vec![0u8; dklen];
+    match keystore.crypto.kdf.as_str() {
+        "scrypt" => {
+            let params = &keystore.crypto.kdfparams;
+            let log_n = (params.n as f64).log2().round() as u8;
+            let scrypt_params = scrypt::Params::new(log_n, params.r, params.p, dklen)
+                .map_err(|error| KeystoreError::Malformed(error.to_string()))?;
+            scrypt::scrypt(passphrase.as_bytes(), &salt, &scrypt_params, &mut derived_key)
+                .map_err(|error| KeystoreError::Malformed(error.to_string()))?;
+        }
+        "pbkdf2" => {
+            pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+                passphrase.as_bytes(),
+                &salt,
+                keystore.crypto.kdfparams.c,
+                &mut derived_key,
+            );
+        }
+        kdf => return Err(KeystoreError::Malformed(format!("Unsupported KDF: {kdf}"))),
+    }
+
+    if derived_key.len() < 32 {
+        return Err(KeystoreError::Malformed(
+            "Derived key is shorter than 32 bytes".to_string(),
+        ));
+    }
+
+    let mut mac_input = derived_key[16..32].to_vec();
+    mac_input.extend_from_slice(&ciphertext);
+    let expected_mac = Keccak256::digest(&mac_input);
+
+    if expected_mac.as_slice() != mac.as_slice() {
+        return Err(KeystoreError::MacMismatch);
+    }
+
+    let mut secret_key_bytes = ciphertext;
+    let mut cipher = Aes128Ctr::new_from_slices(&derived_key[..16], &iv)
+        .map_err(|error| KeystoreError::Malformed(error.to_string()))?;
+    cipher.apply_keystream(&mut secret_key_bytes);
+
+    secret_key_bytes
+        .try_into()
+        .map_err(|_| KeystoreError::Malformed("Decrypted secret key is not 32 bytes".to_string()))
+}
+
+/// Decrypts a Web3 Secret Storage (v3) keystore JSON blob with the given
+/// passphrase and returns a [`GenesisAccount`] ready to hand to the
+/// provider's genesis configuration.
+///
+/// This returns a [`GenesisAccount`] rather than the bare hex-encoded secret
+/// key: the key only ever exists in Rust as a fixed-size byte array (never a
+/// `Debug`/`Display`/`Serialize`-able `String`) until the moment it's written
+/// into the same `JsString`-typed field that a directly user-supplied
+/// `GenesisAccount::secret_key` already goes through, so a caller threading
+/// the result straight into the genesis config never has a bare plaintext
+/// hex string pass through a loggable type on the way.
+#[napi]
+pub fn secret_key_from_keystore(
+    env: Env,
+    keystore_json: String,
+    passphrase: String,
+    balance: BigInt,
+) -> napi::Result<GenesisAccount> {
+    static_assertions::assert_not_impl_all!(JsString: Debug, Display, Serialize);
+
+    let secret_key_bytes = decrypt_keystore(&keystore_json, &passphrase)?;
+
+    // Validate that the decrypted bytes form a valid secret key before handing
+    // a hex string back to JS.
+    k256::SecretKey::from_slice(&secret_key_bytes)
+        .map_err(|error| napi::Error::new(Status::InvalidArg, error.to_string()))?;
+
+    let secret_key = env.create_string(&hex::encode(secret_key_bytes))?;
+
+    Ok(GenesisAccount {
+        secret_key,
+        balance,
+    })
+}
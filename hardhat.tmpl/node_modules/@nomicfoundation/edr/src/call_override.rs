@@ -1,8 +1,8 @@
-use std::sync::mpsc::channel;
+use std::{collections::HashMap, sync::mpsc::channel};
 
 use edr_eth::{Address, Bytes};
 use napi::{
-    bindgen_prelude::{Buffer, Promise},
+    bindgen_prelude::{BigInt, Buffer, Promise},
     threadsafe_function::{
         ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
     },
@@ -18,6 +18,137 @@ use crate::cast::TryCast;
 pub struct CallOverrideResult {
     pub result: Buffer,
     pub should_revert: bool,
+    /// Per-account state overrides to apply to the in-memory state before/
+    /// around the overridden call.
+    pub state_overrides: Option<Vec<StateOverrideOptions>>,
+    /// Additional access list entries that EDR folds into the execution's
+    /// warm address/storage set, on top of the ones already warmed for the
+    /// call being overridden.
+    pub access_list_overrides: Option<Vec<AccessListItem>>,
+    /// Forces the EVM to halt with the given reason instead of returning
+    /// `result`/`should_revert`. Mutually exclusive with `should_revert`.
+    pub halt: Option<ExceptionalHalt>,
+}
+
+/// Indicates that the EVM has experienced an exceptional halt. Mirrors
+/// revm's `HaltReason` and is shared between the call-override path and the
+/// normal call-result path, so JS consumers can distinguish halts from
+/// reverts instead of string-matching error messages.
+#[napi]
+pub enum ExceptionalHalt {
+    OutOfGas,
+    OpcodeNotFound,
+    InvalidFEOpcode,
+    InvalidJump,
+    NotActivated,
+    StackUnderflow,
+    StackOverflow,
+    OutOfOffset,
+    CreateCollision,
+    PrecompileError,
+    NonceOverflow,
+    /// Create init code size exceeds limit (runtime).
+    CreateContractSizeLimit,
+    /// Error on created contract that begins with EF
+    CreateContractStartingWithEF,
+    /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
+    CreateInitCodeSizeLimit,
+    /// EIP-7069: the target of an `EXTCALL`/`EXTDELEGATECALL`/`EXTSTATICCALL`
+    /// is not a valid EOF contract.
+    InvalidEXTCALLTarget,
+}
+
+impl From<ExceptionalHalt> for edr_evm::HaltReason {
+    fn from(value: ExceptionalHalt) -> Self {
+        match value {
+            ExceptionalHalt::OutOfGas => Self::OutOfGas(edr_evm::OutOfGasError::Basic),
+            ExceptionalHalt::OpcodeNotFound => Self::OpcodeNotFound,
+            ExceptionalHalt::InvalidFEOpcode => Self::InvalidFEOpcode,
+            ExceptionalHalt::InvalidJump => Self::InvalidJump,
+            ExceptionalHalt::NotActivated => Self::NotActivated,
+            ExceptionalHalt::StackUnderflow => Self::StackUnderflow,
+            ExceptionalHalt::StackOverflow => Self::StackOverflow,
+            ExceptionalHalt::OutOfOffset => Self::OutOfOffset,
+            ExceptionalHalt::CreateCollision => Self::CreateCollision,
+            ExceptionalHalt::PrecompileError => Self::PrecompileError,
+            ExceptionalHalt::NonceOverflow => Self::NonceOverflow,
+            ExceptionalHalt::CreateContractSizeLimit => Self::CreateContractSizeLimit,
+            ExceptionalHalt::CreateContractStartingWithEF => Self::CreateContractStartingWithEF,
+            ExceptionalHalt::CreateInitCodeSizeLimit => Self::CreateInitCodeSizeLimit,
+            ExceptionalHalt::InvalidEXTCALLTarget => Self::InvalidEXTCALLTarget,
+        }
+    }
+}
+
+/// Whether `storage` fully replaces an account's storage (`state`) or is
+/// merged into the existing storage (`stateDiff`), mirroring the
+/// `eth_call`-style state-override object.
+#[napi(string_enum)]
+pub enum StateOverrideMode {
+    /// Only the provided slots are overridden; all other slots are left
+    /// untouched.
+    StateDiff,
+    /// The account's entire storage is replaced with the provided slots.
+    State,
+}
+
+/// A per-account state override, applied to the in-memory state before/
+/// around a call-override callback's overridden call.
+#[napi(object)]
+pub struct StateOverrideOptions {
+    /// The address of the account to override.
+    pub address: Buffer,
+    /// The account's overridden balance.
+    pub balance: Option<BigInt>,
+    /// The account's overridden nonce.
+    pub nonce: Option<BigInt>,
+    /// The account's overridden code.
+    pub code: Option<Buffer>,
+    /// The account's overridden storage slots, keyed by 32-byte slot.
+    pub storage: Option<HashMap<String, String>>,
+    /// Whether `storage` replaces the account's entire storage or is merged
+    /// into the existing storage. Defaults to `stateDiff`.
+    pub storage_mode: Option<StateOverrideMode>,
+}
+
+impl TryCast<edr_provider::StateOverride> for StateOverrideOptions {
+    type Error = napi::Error;
+
+    fn try_cast(self) -> Result<edr_provider::StateOverride, Self::Error> {
+        let storage = self
+            .storage
+            .map(|storage| {
+                storage
+                    .into_iter()
+                    .map(|(slot, value)| {
+                        let slot: edr_eth::U256 = slot.parse().map_err(|_error| {
+                            napi::Error::new(
+                                Status::InvalidArg,
+                                format!("Invalid storage slot: {slot}"),
+                            )
+                        })?;
+                        let value: edr_eth::U256 = value.parse().map_err(|_error| {
+                            napi::Error::new(
+                                Status::InvalidArg,
+                                format!("Invalid storage value: {value}"),
+                            )
+                        })?;
+
+                        Ok((slot, value))
+                    })
+                    .collect::<Result<_, napi::Error>>()
+            })
+            .transpose()?;
+
+        Ok(edr_provider::StateOverride {
+            address: self.address.try_cast()?,
+            balance: self.balance.map(TryCast::try_cast).transpose()?,
+            nonce: self.nonce.map(TryCast::try_cast).transpose()?,
+            code: self.code.map(|code| Bytes::copy_from_slice(&code)),
+            storage,
+            replace_storage: matches!(self.storage_mode, Some(StateOverrideMode::State)),
+        })
+    }
 }
 
 impl TryCast<Option<edr_provider::CallOverrideResult>> for Option<CallOverrideResult> {
@@ -26,17 +157,67 @@ impl TryCast<Option<edr_provider::CallOverrideResult>> for Option<CallOverrideRe
     fn try_cast(self) -> Result<Option<edr_provider::CallOverrideResult>, Self::Error> {
         match self {
             None => Ok(None),
-            Some(result) => Ok(Some(edr_provider::CallOverrideResult {
-                output: result.result.try_cast()?,
-                should_revert: result.should_revert,
-            })),
+            Some(result) => {
+                let state_overrides = result
+                    .state_overrides
+                    .map(|overrides| {
+                        overrides
+                            .into_iter()
+                            .map(TryCast::try_cast)
+                            .collect::<Result<_, napi::Error>>()
+                    })
+                    .transpose()?
+                    .unwrap_or_default();
+
+                let access_list_overrides = result
+                    .access_list_overrides
+                    .map(|entries| {
+                        entries
+                            .into_iter()
+                            .map(|entry| {
+                                let address: Address = entry.address.try_cast()?;
+                                let storage_keys = entry
+                                    .storage_keys
+                                    .into_iter()
+                                    .map(TryCast::try_cast)
+                                    .collect::<Result<_, napi::Error>>()?;
+
+                                Ok((address, storage_keys))
+                            })
+                            .collect::<Result<_, napi::Error>>()
+                    })
+                    .transpose()?
+                    .unwrap_or_default();
+
+                let halt = result.halt.map(edr_evm::HaltReason::from);
+
+                Ok(Some(edr_provider::CallOverrideResult {
+                    output: result.result.try_cast()?,
+                    should_revert: result.should_revert,
+                    state_overrides,
+                    access_list_overrides,
+                    halt,
+                }))
+            }
         }
     }
 }
 
+/// A single EIP-2930 access list entry: an address together with the storage
+/// slots within it that are pre-warmed for the current transaction.
+#[napi(object)]
+pub struct AccessListItem {
+    pub address: Buffer,
+    pub storage_keys: Vec<Buffer>,
+}
+
 struct CallOverrideCall {
     contract_address: Address,
     data: Bytes,
+    caller: Address,
+    value: edr_eth::U256,
+    gas: u64,
+    access_list: Vec<(Address, Vec<edr_eth::B256>)>,
 }
 
 #[derive(Clone)]
@@ -64,7 +245,53 @@ impl CallOverrideCallback {
                     .create_buffer_with_data(ctx.value.data.to_vec())?
                     .into_raw();
 
-                Ok(vec![address, data])
+                let caller = ctx
+                    .env
+                    .create_buffer_with_data(ctx.value.caller.to_vec())?
+                    .into_raw();
+
+                let value = BigInt::from(ctx.value.value)
+                    .into_unknown(ctx.env)?;
+
+                let gas = ctx
+                    .env
+                    .create_bigint_from_u64(ctx.value.gas)?
+                    .into_unknown()?;
+
+                let mut access_list = ctx
+                    .env
+                    .create_array_with_length(ctx.value.access_list.len())?;
+                for (index, (address, storage_keys)) in
+                    ctx.value.access_list.into_iter().enumerate()
+                {
+                    let mut entry = ctx.env.create_object()?;
+
+                    let address = ctx
+                        .env
+                        .create_buffer_with_data(address.to_vec())?
+                        .into_raw();
+                    entry.set_named_property("address", address)?;
+
+                    let mut keys = ctx
+                        .env
+                        .create_array_with_length(storage_keys.len())?;
+                    for (key_index, key) in storage_keys.into_iter().enumerate() {
+                        let key = ctx.env.create_buffer_with_data(key.to_vec())?.into_raw();
+                        keys.set_element(key_index as u32, key)?;
+                    }
+                    entry.set_named_property("storageKeys", keys)?;
+
+                    access_list.set_element(index as u32, entry)?;
+                }
+
+                Ok(vec![
+                    address,
+                    data,
+                    caller,
+                    value,
+                    gas,
+                    access_list.coerce_to_object()?.into_unknown(),
+                ])
             },
         )?;
 
@@ -78,10 +305,15 @@ impl CallOverrideCallback {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn call_override(
         &self,
         contract_address: Address,
         data: Bytes,
+        caller: Address,
+        value: edr_eth::U256,
+        gas: u64,
+        access_list: Vec<(Address, Vec<edr_eth::B256>)>,
     ) -> Option<edr_provider::CallOverrideResult> {
         let (sender, receiver) = channel();
 
@@ -90,6 +322,10 @@ impl CallOverrideCallback {
             CallOverrideCall {
                 contract_address,
                 data,
+                caller,
+                value,
+                gas,
+                access_list,
             },
             ThreadsafeFunctionCallMode::Blocking,
             move |result: Promise<Option<CallOverrideResult>>| {
@@ -1,6 +1,5 @@
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, path::PathBuf, sync::Arc};
 
-#[cfg(feature = "tracing")]
 use napi::Status;
 use napi_derive::napi;
 use tracing_subscriber::{prelude::*, EnvFilter, Registry};
@@ -19,65 +18,166 @@ impl Deref for EdrContext {
     }
 }
 
+/// The tracing output format to use for an [`EdrContext`].
+#[napi(string_enum)]
+pub enum TracingOutputFormat {
+    /// A folded-stack file, readable by `inferno`/`flamegraph.pl`. This is the
+    /// default behavior.
+    Folded,
+    /// Chrome/Perfetto-tracing-compatible JSON, importable via
+    /// `chrome://tracing` or <https://ui.perfetto.dev>.
+    Chrome,
+    /// Newline-delimited JSON, one event per line.
+    Json,
+}
+
+/// Configuration for [`EdrContext::with_config`].
+#[napi(object)]
+pub struct EdrContextConfig {
+    /// The `tracing` filter directives to use, e.g. `"edr_evm=debug"`. If not
+    /// provided, the `RUST_LOG` environment variable is used, falling back to
+    /// `tracing`'s default filter when unset.
+    pub filter: Option<String>,
+    /// The tracing output format. Defaults to [`TracingOutputFormat::Folded`].
+    pub output: Option<TracingOutputFormat>,
+    /// The file path to write the tracing output to. Defaults to
+    /// `tracing.folded`, `tracing.chrome.json`, or `tracing.jsonl`, depending
+    /// on `output`.
+    pub output_path: Option<String>,
+}
+
 #[napi]
 impl EdrContext {
     #[doc = "Creates a new [`EdrContext`] instance. Should only be called once!"]
     #[napi(constructor)]
     pub fn new() -> napi::Result<Self> {
-        let context = Context::new()?;
+        let context = Context::new(EdrContextConfig {
+            filter: None,
+            output: None,
+            output_path: None,
+        })?;
 
         Ok(Self {
             inner: Arc::new(context),
         })
     }
+
+    #[doc = "Creates a new [`EdrContext`] instance with a custom tracing configuration. Should only be called once!"]
+    #[napi(factory)]
+    pub fn with_config(config: EdrContextConfig) -> napi::Result<Self> {
+        let context = Context::new(config)?;
+
+        Ok(Self {
+            inner: Arc::new(context),
+        })
+    }
+}
+
+/// An error that occurred while constructing an [`EdrContext`].
+#[derive(Debug, thiserror::Error)]
+pub enum ContextError {
+    /// `EdrContext` (or `Context::new`) was called more than once per
+    /// process. Only the first call can install the global `tracing`
+    /// subscriber.
+    #[error(
+        "Failed to set global tracing subscriber: a subscriber is already set. \
+         Please only initialize `EdrContext` once per process."
+    )]
+    AlreadyInitialized,
+    /// Failed to open the configured tracing output file.
+    #[error("Failed to create tracing output file `{path}`: {source}")]
+    OutputFile {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+impl From<ContextError> for napi::Error {
+    fn from(value: ContextError) -> Self {
+        napi::Error::new(Status::GenericFailure, value.to_string())
+    }
+}
+
+/// The resources that must stay alive for the lifetime of the process in
+/// order for buffered tracing output to be flushed on drop.
+#[derive(Debug)]
+enum TracingGuard {
+    Folded(tracing_flame::FlushGuard<std::io::BufWriter<std::fs::File>>),
+    Chrome(tracing_chrome::FlushGuard),
+    /// The JSON fmt layer flushes eagerly and doesn't need a guard.
+    None,
 }
 
 #[derive(Debug)]
 pub struct Context {
-    #[cfg(feature = "tracing")]
-    _tracing_write_guard: tracing_flame::FlushGuard<std::io::BufWriter<std::fs::File>>,
+    _tracing_guard: TracingGuard,
 }
 
 impl Context {
-    /// Creates a new [`Context`] instance. Should only be called once!
-    pub fn new() -> napi::Result<Self> {
-        let fmt_layer = tracing_subscriber::fmt::layer()
-            .with_file(true)
-            .with_line_number(true)
-            .with_thread_ids(true)
-            .with_target(false)
-            .with_level(true)
-            .with_filter(EnvFilter::from_default_env());
-
-        let subscriber = Registry::default().with(fmt_layer);
-
-        #[cfg(feature = "tracing")]
-        let (flame_layer, guard) = {
-            let (flame_layer, guard) = tracing_flame::FlameLayer::with_file("tracing.folded")
-                .map_err(|err| {
-                    napi::Error::new(
-                        Status::GenericFailure,
-                        format!("Failed to create tracing.folded file with error: {err:?}"),
-                    )
-                })?;
-
-            let flame_layer = flame_layer.with_empty_samples(false);
-            (flame_layer, guard)
-        };
+    /// Creates a new [`Context`] instance using the tracing configuration in
+    /// `config`; output defaults to folded-stack when `config.output` is not
+    /// set. Should only be called once!
+    pub fn new(config: EdrContextConfig) -> napi::Result<Self> {
+        let filter = config
+            .filter
+            .map_or_else(EnvFilter::from_default_env, EnvFilter::new);
 
-        #[cfg(feature = "tracing")]
-        let subscriber = subscriber.with(flame_layer);
+        let (guard, subscriber) = match config.output.unwrap_or(TracingOutputFormat::Folded) {
+            TracingOutputFormat::Folded => {
+                let path = config
+                    .output_path
+                    .unwrap_or_else(|| "tracing.folded".to_string());
+
+                let (flame_layer, guard) = tracing_flame::FlameLayer::with_file(&path)
+                    .map_err(|source| ContextError::OutputFile { path, source })?;
+                let flame_layer = flame_layer.with_empty_samples(false).with_filter(filter);
+
+                (
+                    TracingGuard::Folded(guard),
+                    Box::new(Registry::default().with(flame_layer))
+                        as Box<dyn tracing::Subscriber + Send + Sync>,
+                )
+            }
+            TracingOutputFormat::Chrome => {
+                let path = config
+                    .output_path
+                    .unwrap_or_else(|| "tracing.chrome.json".to_string());
+
+                let (chrome_layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+                    .file(PathBuf::from(path))
+                    .build();
+                let chrome_layer = chrome_layer.with_filter(filter);
+
+                (
+                    TracingGuard::Chrome(guard),
+                    Box::new(Registry::default().with(chrome_layer))
+                        as Box<dyn tracing::Subscriber + Send + Sync>,
+                )
+            }
+            TracingOutputFormat::Json => {
+                let fmt_layer = tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_file(true)
+                    .with_line_number(true)
+                    .with_thread_ids(true)
+                    .with_target(false)
+                    .with_level(true)
+                    .with_filter(filter);
+
+                (
+                    TracingGuard::None,
+                    Box::new(Registry::default().with(fmt_layer))
+                        as Box<dyn tracing::Subscriber + Send + Sync>,
+                )
+            }
+        };
 
-        if let Err(error) = tracing::subscriber::set_global_default(subscriber) {
-            println!(
-                "Failed to set global tracing subscriber with error: {error}\n\
-                Please only initialize EdrContext once per process to avoid this error."
-            );
-        }
+        tracing::subscriber::set_global_default(subscriber)
+            .map_err(|_error| ContextError::AlreadyInitialized)?;
 
         Ok(Self {
-            #[cfg(feature = "tracing")]
-            _tracing_write_guard: guard,
+            _tracing_guard: guard,
         })
     }
 }
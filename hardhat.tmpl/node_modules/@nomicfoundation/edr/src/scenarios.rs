@@ -1,23 +1,99 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 
-use edr_provider::ProviderRequest;
-use napi::tokio::{fs::File, io::AsyncWriteExt, sync::Mutex};
+use async_compression::tokio::{
+    bufread::{GzipDecoder, ZstdDecoder},
+    write::{GzipEncoder, ZstdEncoder},
+};
+use edr_eth::remote::jsonrpc;
+use edr_provider::{time::CurrentTime, ProviderRequest};
+use napi::tokio::{
+    fs::File,
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    runtime,
+    sync::Mutex,
+};
+use napi_derive::napi;
 use rand::{distributions::Alphanumeric, Rng};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 const SCENARIO_FILE_PREFIX: &str = "EDR_SCENARIO_PREFIX";
+const SCENARIO_COMPRESSION: &str = "EDR_SCENARIO_COMPRESSION";
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct ScenarioConfig {
     provider_config: edr_scenarios::ScenarioProviderConfig,
     logger_enabled: bool,
 }
 
+/// The compression applied to a scenario file. Chosen when writing via the
+/// `EDR_SCENARIO_COMPRESSION` environment variable (`"gzip"` or `"zstd"`;
+/// anything else, including unset, means uncompressed), and encoded in the
+/// file's extension so that [`replay_scenario`] can infer it back from the
+/// path alone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ScenarioCompression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl ScenarioCompression {
+    fn from_env() -> Self {
+        match std::env::var(SCENARIO_COMPRESSION).as_deref() {
+            Ok("gzip") => Self::Gzip,
+            Ok("zstd") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    fn from_path(path: &str) -> Self {
+        if path.ends_with(".gz") {
+            Self::Gzip
+        } else if path.ends_with(".zst") {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::None => "json",
+            Self::Gzip => "json.gz",
+            Self::Zstd => "json.zst",
+        }
+    }
+
+    fn wrap_writer(self, file: File) -> Box<dyn AsyncWrite + Send + Unpin> {
+        match self {
+            Self::None => Box::new(file),
+            Self::Gzip => Box::new(GzipEncoder::new(file)),
+            Self::Zstd => Box::new(ZstdEncoder::new(file)),
+        }
+    }
+
+    fn wrap_reader(self, file: File) -> BufReader<Box<dyn AsyncRead + Send + Unpin>> {
+        let file = BufReader::new(file);
+        let reader: Box<dyn AsyncRead + Send + Unpin> = match self {
+            Self::None => Box::new(file),
+            Self::Gzip => Box::new(GzipDecoder::new(file)),
+            Self::Zstd => Box::new(ZstdDecoder::new(file)),
+        };
+
+        BufReader::new(reader)
+    }
+}
+
 pub(crate) async fn scenario_file(
     provider_config: &edr_provider::ProviderConfig,
     logger_enabled: bool,
-) -> Result<Option<Mutex<File>>, napi::Error> {
+) -> Result<Option<Mutex<Box<dyn AsyncWrite + Send + Unpin>>>, napi::Error> {
     if let Ok(scenario_prefix) = std::env::var(SCENARIO_FILE_PREFIX) {
+        let compression = ScenarioCompression::from_env();
+
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards")
@@ -28,8 +104,12 @@ pub(crate) async fn scenario_file(
             .map(char::from)
             .collect::<String>();
 
-        let mut scenario_file =
-            File::create(format!("{scenario_prefix}_{timestamp}_{suffix}.json")).await?;
+        let file = File::create(format!(
+            "{scenario_prefix}_{timestamp}_{suffix}.{}",
+            compression.extension()
+        ))
+        .await?;
+        let mut scenario_file = compression.wrap_writer(file);
 
         let config = ScenarioConfig {
             provider_config: provider_config.clone().into(),
@@ -38,6 +118,7 @@ pub(crate) async fn scenario_file(
         let mut line = serde_json::to_string(&config)?;
         line.push('\n');
         scenario_file.write_all(line.as_bytes()).await?;
+        scenario_file.flush().await?;
 
         Ok(Some(Mutex::new(scenario_file)))
     } else {
@@ -45,15 +126,207 @@ pub(crate) async fn scenario_file(
     }
 }
 
-pub(crate) async fn write_request(
-    scenario_file: &Mutex<File>,
+/// One recorded request/response exchange, as persisted to and read back
+/// from a scenario file. The response is the JSON-RPC response body exactly
+/// as `Provider.handleRequest` returned it at capture time, used by
+/// [`replay_scenario`] as the baseline to diff a replayed response against.
+/// Traces and logger output aren't persisted: traces aren't `Serialize`,
+/// and the replay logger discards everything it's given (see
+/// [`ReplayLogger`]), so divergence reporting is scoped to the response
+/// body.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ScenarioExchange {
+    request: ProviderRequest,
+    response: serde_json::Value,
+}
+
+pub(crate) async fn write_exchange(
+    scenario_file: &Mutex<Box<dyn AsyncWrite + Send + Unpin>>,
     request: &ProviderRequest,
+    response: &serde_json::Value,
 ) -> napi::Result<()> {
-    let mut line = serde_json::to_string(request)?;
+    let exchange = ScenarioExchange {
+        request: request.clone(),
+        response: response.clone(),
+    };
+    let mut line = serde_json::to_string(&exchange)?;
     line.push('\n');
     {
         let mut scenario_file = scenario_file.lock().await;
         scenario_file.write_all(line.as_bytes()).await?;
+        scenario_file.flush().await?;
     }
     Ok(())
 }
+
+/// A [`edr_provider::Logger`] that discards everything it's given. Scenario
+/// replay has no JS-side console to print to, so the scenario's own
+/// `logger_enabled` flag is preserved (in case replaying code branches on
+/// it) but no log line is ever actually produced.
+struct ReplayLogger {
+    is_enabled: bool,
+}
+
+impl ReplayLogger {
+    fn new(is_enabled: bool) -> Self {
+        Self { is_enabled }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ReplayLoggerError {}
+
+impl edr_provider::Logger for ReplayLogger {
+    type BlockchainError = edr_evm::blockchain::BlockchainError;
+    type LoggerError = ReplayLoggerError;
+
+    fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    fn set_is_enabled(&mut self, is_enabled: bool) {
+        self.is_enabled = is_enabled;
+    }
+
+    fn log_call(
+        &mut self,
+        _spec_id: edr_eth::SpecId,
+        _transaction: &edr_evm::ExecutableTransaction,
+        _result: &edr_provider::CallResult,
+    ) -> Result<(), Self::LoggerError> {
+        Ok(())
+    }
+
+    fn log_estimate_gas_failure(
+        &mut self,
+        _spec_id: edr_eth::SpecId,
+        _transaction: &edr_evm::ExecutableTransaction,
+        _failure: &edr_provider::EstimateGasFailure,
+    ) -> Result<(), Self::LoggerError> {
+        Ok(())
+    }
+
+    fn log_multicall(
+        &mut self,
+        _spec_id: edr_eth::SpecId,
+        _transactions: &[edr_evm::ExecutableTransaction],
+        _results: &[edr_provider::CallResult],
+    ) -> Result<(), Self::LoggerError> {
+        Ok(())
+    }
+
+    fn log_interval_mined(
+        &mut self,
+        _spec_id: edr_eth::SpecId,
+        _mining_result: &edr_provider::DebugMineBlockResult<Self::BlockchainError>,
+    ) -> Result<(), Self::LoggerError> {
+        Ok(())
+    }
+
+    fn log_mined_block(
+        &mut self,
+        _spec_id: edr_eth::SpecId,
+        _mining_results: &[edr_provider::DebugMineBlockResult<Self::BlockchainError>],
+    ) -> Result<(), Self::LoggerError> {
+        Ok(())
+    }
+
+    fn log_send_transaction(
+        &mut self,
+        _spec_id: edr_eth::SpecId,
+        _transaction: &edr_evm::ExecutableTransaction,
+        _mining_results: &[edr_provider::DebugMineBlockResult<Self::BlockchainError>],
+    ) -> Result<(), Self::LoggerError> {
+        Ok(())
+    }
+
+    fn print_method_logs(
+        &mut self,
+        _method: &str,
+        _error: Option<&edr_provider::ProviderError<Self::LoggerError>>,
+    ) -> Result<(), Self::LoggerError> {
+        Ok(())
+    }
+}
+
+/// One request's worth of result from [`replay_scenario`].
+#[napi(object)]
+pub struct ScenarioReplayResponse {
+    /// The JSON-RPC response, serialized the same way `Provider.handleRequest`
+    /// would return it.
+    pub response: String,
+    /// The response recorded at capture time, for comparison.
+    pub recorded_response: String,
+    /// Whether `response` differs from `recorded_response`.
+    pub diverged: bool,
+    /// Wall-clock time the provider spent handling the request, in
+    /// milliseconds.
+    pub duration_millis: f64,
+}
+
+/// Replays a scenario file previously captured via `EDR_SCENARIO_PREFIX`
+/// (see [`scenario_file`]/[`write_exchange`]): reconstructs the provider it
+/// was recorded against from the file's header line, then feeds every
+/// subsequent request through that provider in the order it was recorded,
+/// diffing each replayed response against the one recorded at capture time.
+/// This turns a scenario file into a regression-testing and
+/// bug-reproduction tool: attach one to an issue, and a maintainer can
+/// replay it deterministically to confirm a fix resolves the divergence.
+/// Transparently decompresses `.json.gz`/`.json.zst` files based on their
+/// extension.
+#[napi]
+pub async fn replay_scenario(path: String) -> napi::Result<Vec<ScenarioReplayResponse>> {
+    let compression = ScenarioCompression::from_path(&path);
+    let file = File::open(&path).await?;
+    let mut lines = compression.wrap_reader(file).lines();
+
+    let header = lines.next_line().await?.ok_or_else(|| {
+        napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Scenario file `{path}` has no header line"),
+        )
+    })?;
+    let config: ScenarioConfig = serde_json::from_str(&header)?;
+    let provider_config = edr_provider::ProviderConfig::try_from(config.provider_config)?;
+
+    let runtime = runtime::Handle::current();
+    let logger = Box::new(ReplayLogger::new(config.logger_enabled));
+    let subscriber_callback: Box<dyn Fn(edr_provider::SubscriptionEvent) + Send + Sync> =
+        Box::new(|_event| {});
+
+    let provider = edr_provider::Provider::new(
+        runtime.clone(),
+        logger,
+        subscriber_callback,
+        provider_config,
+        CurrentTime,
+    )
+    .map_err(|error| napi::Error::new(napi::Status::GenericFailure, error.to_string()))?;
+    let provider = Arc::new(provider);
+
+    let mut responses = Vec::new();
+    while let Some(line) = lines.next_line().await? {
+        let exchange: ScenarioExchange = serde_json::from_str(&line)?;
+        let provider = provider.clone();
+
+        let started_at = Instant::now();
+        let response = runtime
+            .spawn_blocking(move || provider.handle_request(exchange.request))
+            .await
+            .map_err(|error| napi::Error::new(napi::Status::GenericFailure, error.to_string()))?;
+        let duration_millis = started_at.elapsed().as_secs_f64() * 1000.0;
+
+        let response = jsonrpc::ResponseData::from(response.map(|response| response.result));
+        let response = serde_json::to_value(&response)?;
+        let diverged = response != exchange.response;
+
+        responses.push(ScenarioReplayResponse {
+            response: serde_json::to_string(&response)?,
+            recorded_response: serde_json::to_string(&exchange.response)?,
+            diverged,
+            duration_millis,
+        });
+    }
+
+    Ok(responses)
+}
@@ -7,7 +7,7 @@ use super::solidity_stack_trace::{RevertErrorStackTraceEntry, SolidityStackTrace
 use crate::trace::return_data::ReturnData;
 
 #[napi]
-fn print_stack_trace(trace: SolidityStackTrace) -> napi::Result<()> {
+fn print_stack_trace(trace: SolidityStackTrace, custom_errors: Vec<String>) -> napi::Result<()> {
     let entry_values = trace
         .into_iter()
         .map(|entry| match entry {
@@ -36,16 +36,37 @@ fn print_stack_trace(trace: SolidityStackTrace) -> napi::Result<()> {
             Either24::X(entry) => serde_json::to_value(entry),
             // Decode the error message from the return data
             Either24::E(entry @ RevertErrorStackTraceEntry { .. }) => {
-                use serde::de::Error;
+                let return_data = ReturnData::new(entry.return_data.clone());
 
-                let decoded_error_msg = ReturnData::new(entry.return_data.clone())
-                    .decode_error()
-                    .map_err(|e| {
-                    serde_json::Error::custom(format_args!("Error decoding return data: {e}"))
-                })?;
+                // Prefer the standard `Error(string)` message; fall back to
+                // matching a caller-supplied custom error; if neither
+                // applies (e.g. a bare `Panic(uint256)`), leave the entry's
+                // existing `message` as-is rather than failing the whole
+                // trace over one undecodable entry.
+                let decoded_message = return_data.decode_error().ok().or_else(|| {
+                    return_data
+                        .decode_custom_error(custom_errors.clone())
+                        .ok()
+                        .flatten()
+                        .map(|decoded| {
+                            let args = decoded
+                                .args
+                                .iter()
+                                .map(|arg| match &arg.name {
+                                    Some(name) => format!("{name}: {}", arg.value),
+                                    None => arg.value.clone(),
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", ");
+
+                            format!("{}({args})", decoded.name)
+                        })
+                });
 
                 let mut value = serde_json::to_value(entry)?;
-                value["message"] = decoded_error_msg.into();
+                if let Some(decoded_message) = decoded_message {
+                    value["message"] = decoded_message.into();
+                }
                 Ok(value)
             }
         })
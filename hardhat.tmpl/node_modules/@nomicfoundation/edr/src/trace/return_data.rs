@@ -1,5 +1,7 @@
 //! Rewrite of `hardhat-network/provider/return-data.ts` from Hardhat.
 
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_json_abi::Error as AbiError;
 use alloy_sol_types::SolError;
 use napi::bindgen_prelude::{BigInt, Uint8Array};
 use napi_derive::napi;
@@ -81,4 +83,134 @@ impl ReturnData {
             words: result._0.as_limbs().to_vec(),
         })
     }
+
+    /// Decodes `self.value` against `abi_errors` (full human-readable
+    /// Solidity error signatures, e.g.
+    /// `"InsufficientBalance(uint256 available, uint256 required)"`),
+    /// matching by 4-byte selector. Returns `None` when the return data's
+    /// selector doesn't match any of the supplied fragments, in which case
+    /// callers should fall back to [`Self::decode_error`]/
+    /// [`Self::decode_panic`].
+    #[napi]
+    pub fn decode_custom_error(
+        &self,
+        abi_errors: Vec<String>,
+    ) -> napi::Result<Option<DecodedCustomError>> {
+        let Some(selector) = self.selector else {
+            return Ok(None);
+        };
+
+        for signature in abi_errors {
+            let error = AbiError::parse(&signature).map_err(|err| {
+                napi::Error::new(
+                    napi::Status::InvalidArg,
+                    format!("Invalid error signature `{signature}`: {err}"),
+                )
+            })?;
+
+            if error.selector().0 != selector {
+                continue;
+            }
+
+            let param_types = error
+                .inputs
+                .iter()
+                .map(|param| {
+                    DynSolType::parse(&param.ty).map_err(|err| {
+                        napi::Error::new(
+                            napi::Status::InvalidArg,
+                            format!("Invalid parameter type `{}`: {err}", param.ty),
+                        )
+                    })
+                })
+                .collect::<napi::Result<Vec<_>>>()?;
+
+            let decoded = DynSolType::Tuple(param_types)
+                .abi_decode_sequence(&self.value[4..])
+                .map_err(|err| {
+                    napi::Error::new(
+                        napi::Status::InvalidArg,
+                        format!("Failed decoding `{signature}`: {err}"),
+                    )
+                })?;
+
+            let DynSolValue::Tuple(values) = decoded else {
+                unreachable!("decoding a Tuple type always yields a Tuple value")
+            };
+
+            let args = error
+                .inputs
+                .iter()
+                .zip(values.iter())
+                .map(|(param, value)| DecodedErrorArgument {
+                    name: if param.name.is_empty() {
+                        None
+                    } else {
+                        Some(param.name.clone())
+                    },
+                    solidity_type: param.ty.clone(),
+                    value: format_dyn_sol_value(value),
+                })
+                .collect();
+
+            return Ok(Some(DecodedCustomError {
+                name: error.name.clone(),
+                args,
+            }));
+        }
+
+        Ok(None)
+    }
+}
+
+/// A single custom-error argument, decoded via [`ReturnData::decode_custom_error`].
+#[napi(object)]
+#[derive(Clone)]
+pub struct DecodedErrorArgument {
+    /// The argument's name, as declared in the Solidity error definition.
+    /// `None` for unnamed arguments.
+    pub name: Option<String>,
+    pub solidity_type: String,
+    /// The argument's value, rendered the way Solidity would print it (e.g.
+    /// decimal for integers, `0x`-prefixed hex for addresses/bytes).
+    pub value: String,
+}
+
+/// The result of [`ReturnData::decode_custom_error`]: the matched error's
+/// name and its decoded, named arguments.
+#[napi(object)]
+#[derive(Clone)]
+pub struct DecodedCustomError {
+    pub name: String,
+    pub args: Vec<DecodedErrorArgument>,
+}
+
+/// Renders a [`DynSolValue`] the way Solidity would print it.
+fn format_dyn_sol_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::FixedBytes(value, size) => format!("0x{}", hex::encode(&value[..*size])),
+        DynSolValue::Address(value) => value.to_string(),
+        DynSolValue::Function(value) => format!("0x{}", hex::encode(value.as_slice())),
+        DynSolValue::Bytes(value) => format!("0x{}", hex::encode(value)),
+        DynSolValue::String(value) => value.clone(),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(format_dyn_sol_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        DynSolValue::Tuple(values) | DynSolValue::CustomStruct { tuple: values, .. } => format!(
+            "({})",
+            values
+                .iter()
+                .map(format_dyn_sol_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
 }
@@ -1,4 +1,5 @@
 mod config;
+mod retry;
 
 use std::sync::Arc;
 
@@ -6,7 +7,9 @@ use edr_provider::{time::CurrentTime, InvalidRequestReason};
 use edr_rpc_eth::jsonrpc;
 use edr_solidity::contract_decoder::ContractDecoder;
 use napi::{
-    bindgen_prelude::Uint8Array, tokio::runtime, Either, Env, JsFunction, JsObject, Status,
+    bindgen_prelude::{BigInt, Buffer, Uint8Array},
+    tokio::runtime,
+    Either, Env, JsFunction, JsObject, Status,
 };
 use napi_derive::napi;
 
@@ -15,10 +18,23 @@ use crate::{
     call_override::CallOverrideCallback,
     context::EdrContext,
     logger::{Logger, LoggerConfig, LoggerError},
-    subscribe::SubscriberCallback,
+    subscribe::{HistoricalStreamCallback, HistoricalStreamEvent, SubscriberCallback},
     trace::{solidity_stack_trace::SolidityStackTrace, RawTrace},
 };
 
+/// The maximum number of blocks fetched per page while backfilling a
+/// historical block range. Each block in a page is still fetched with its
+/// own `eth_getBlockByNumber` and `eth_getLogs` requests.
+const HISTORICAL_STREAM_PAGE_SIZE: u64 = 1_000;
+
+/// An eth log filter restricting a historical block-range stream to specific
+/// emitters/topics, mirroring the shape accepted by `eth_getLogs`.
+#[napi(object)]
+pub struct HistoricalStreamFilter {
+    pub address: Option<Vec<Buffer>>,
+    pub topics: Option<Vec<Option<Vec<Buffer>>>>,
+}
+
 /// A JSON-RPC provider for Ethereum.
 #[napi]
 pub struct Provider {
@@ -142,6 +158,7 @@ impl Provider {
                     })
                     .map(|json| Response {
                         solidity_trace: None,
+                        gas_limit_too_low: None,
                         data: Either::A(json),
                         traces: Vec::new(),
                     });
@@ -153,28 +170,49 @@ impl Provider {
             crate::scenarios::write_request(scenario_file, &request).await?;
         }
 
+        // A bare `OutOfGas` only means "the binary search's probe was too low" when
+        // it comes from the gas-search path of `eth_call`/`eth_estimateGas`; the
+        // exact same failure reason from e.g. `eth_sendTransaction` just means the
+        // caller supplied too little gas, an ordinary revert-shaped failure. Only
+        // the former should be reported as `GasEstimationGasLimitTooLowError`
+        // instead of the usual decoded stack trace, so we need the method name
+        // before `request` is moved into `handle_request` below.
+        let is_gas_search_method = matches!(
+            serde_json::from_str::<serde_json::Value>(&json_request)
+                .ok()
+                .and_then(|value| value.get("method").and_then(|method| method.as_str().map(str::to_string))),
+            Some(method) if method == "eth_call" || method == "eth_estimateGas"
+        );
+
         let mut response = runtime::Handle::current()
             .spawn_blocking(move || provider.handle_request(request))
             .await
             .map_err(|e| napi::Error::new(Status::GenericFailure, e.to_string()))?;
 
-        // We can take the solidity trace as it won't be used for anything else
-        let solidity_trace = response.as_mut().err().and_then(|error| {
-            if let edr_provider::ProviderError::TransactionFailed(failure) = error {
-                if matches!(
-                    failure.failure.reason,
-                    edr_provider::TransactionFailureReason::OutOfGas(_)
-                ) {
-                    None
+        // We can take the solidity trace as it won't be used for anything else.
+        let (solidity_trace, gas_limit_too_low) = response.as_mut().err().map_or(
+            (None, None),
+            |error| {
+                if let edr_provider::ProviderError::TransactionFailed(failure) = error {
+                    if is_gas_search_method {
+                        if let edr_provider::TransactionFailureReason::OutOfGas(gas_limit) =
+                            &failure.failure.reason
+                        {
+                            return (None, Some(*gas_limit));
+                        }
+                    }
+
+                    (
+                        Some(Arc::new(std::mem::take(
+                            &mut failure.failure.solidity_trace,
+                        ))),
+                        None,
+                    )
                 } else {
-                    Some(Arc::new(std::mem::take(
-                        &mut failure.failure.solidity_trace,
-                    )))
+                    (None, None)
                 }
-            } else {
-                None
-            }
-        });
+            },
+        );
 
         // We can take the traces as they won't be used for anything else
         let traces = match &mut response {
@@ -211,6 +249,7 @@ impl Provider {
                 });
                 Response {
                     solidity_trace,
+                    gas_limit_too_low,
                     data,
                     traces: traces.into_iter().map(Arc::new).collect(),
                 }
@@ -246,6 +285,116 @@ impl Provider {
     pub fn set_verbose_tracing(&self, verbose_tracing: bool) {
         self.provider.set_verbose_tracing(verbose_tracing);
     }
+
+    /// Checks `client_version` (a `web3_clientVersion` response, e.g.
+    /// `"Geth/v1.13.5-stable/..."`) against the known-compatible version
+    /// ranges, returning a warning message to log if it falls outside. This
+    /// is the call site for [`retry::check_node_version_compatibility`]: the
+    /// actual `web3_clientVersion` handshake happens in the forking JSON-RPC
+    /// client, which isn't part of this binding layer, so it calls this
+    /// method with the response once it has one, and logs whatever warning
+    /// comes back.
+    #[napi]
+    pub fn check_remote_node_version(&self, client_version: String) -> Option<String> {
+        retry::check_node_version_compatibility(&client_version)
+    }
+
+    /// Backfills a fixed `[fromBlock, toBlock]` range, delivering each
+    /// block's header and matching logs through `callback` in ascending
+    /// order, followed by a final "caught up" event. Each block is fetched
+    /// with one `eth_getBlockByNumber` and one `eth_getLogs` request through
+    /// the provider's normal request path.
+    #[napi(ts_return_type = "Promise<void>")]
+    pub fn stream_historical_blocks(
+        &self,
+        env: Env,
+        from_block: BigInt,
+        to_block: BigInt,
+        filter: Option<HistoricalStreamFilter>,
+        #[napi(ts_arg_type = "(event: HistoricalStreamEvent) => void")] callback: JsFunction,
+    ) -> napi::Result<JsObject> {
+        let provider = self.provider.clone();
+        let callback = HistoricalStreamCallback::new(&env, callback)?;
+
+        let from_block: u64 = from_block.get_u64().1;
+        let to_block: u64 = to_block.get_u64().1;
+
+        let (deferred, promise) = env.create_deferred()?;
+        runtime::Handle::current().spawn_blocking(move || {
+            let result = (|| -> napi::Result<()> {
+                let mut cursor = from_block;
+                while cursor <= to_block {
+                    let page_end =
+                        cursor.saturating_add(HISTORICAL_STREAM_PAGE_SIZE - 1).min(to_block);
+
+                    for block_number in cursor..=page_end {
+                        let logs_params = serde_json::json!([{
+                            "fromBlock": format!("0x{block_number:x}"),
+                            "toBlock": format!("0x{block_number:x}"),
+                            "address": filter.as_ref().and_then(|filter| filter.address.as_ref())
+                                .map(|addresses| addresses.iter().map(|address| format!("0x{}", hex::encode(address.as_ref()))).collect::<Vec<_>>()),
+                            "topics": filter.as_ref().and_then(|filter| filter.topics.as_ref())
+                                .map(|topics| topics.iter().map(|topic| {
+                                    topic.as_ref().map(|topic| topic.iter().map(|t| format!("0x{}", hex::encode(t.as_ref()))).collect::<Vec<_>>())
+                                }).collect::<Vec<_>>()),
+                        }]);
+
+                        let block = request_json(&provider, "eth_getBlockByNumber", serde_json::json!([format!("0x{block_number:x}"), false]))?;
+                        let logs = request_json(&provider, "eth_getLogs", logs_params)?;
+                        let logs = match logs {
+                            serde_json::Value::Array(logs) => logs,
+                            _ => Vec::new(),
+                        };
+
+                        callback.call(HistoricalStreamEvent {
+                            block: Some(block),
+                            logs,
+                            caught_up: false,
+                        });
+                    }
+
+                    cursor = page_end + 1;
+                }
+
+                callback.call(HistoricalStreamEvent {
+                    block: None,
+                    logs: Vec::new(),
+                    caught_up: true,
+                });
+
+                Ok(())
+            })();
+
+            deferred.resolve(|_env| result);
+        });
+
+        Ok(promise)
+    }
+}
+
+/// Issues a single internal JSON-RPC request against `provider` and returns
+/// the decoded `result` value, used by `stream_historical_blocks` to reuse
+/// the normal request path rather than duplicating block/log assembly logic.
+fn request_json(
+    provider: &edr_provider::Provider<LoggerError>,
+    method: &str,
+    params: serde_json::Value,
+) -> napi::Result<serde_json::Value> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+    let request = serde_json::from_value(request)
+        .map_err(|error| napi::Error::new(Status::GenericFailure, error.to_string()))?;
+
+    let response = provider
+        .handle_request(request)
+        .map_err(|error| napi::Error::new(Status::GenericFailure, error.to_string()))?;
+
+    serde_json::to_value(response.result)
+        .map_err(|error| napi::Error::new(Status::GenericFailure, error.to_string()))
 }
 
 /// Tracing config for Solidity stack trace generation.
@@ -323,6 +472,15 @@ pub struct Response {
     /// When a transaction fails to execute, the provider returns a trace of the
     /// transaction.
     solidity_trace: Option<SolidityTraceData>,
+    /// Set instead of `solidity_trace` when the request was `eth_call` or
+    /// `eth_estimateGas` and the transaction failed with a bare `OutOfGas`,
+    /// i.e. the gas-search binary search probed this gas limit and found it
+    /// insufficient. Carries that gas limit so `stack_trace` can report a
+    /// `GasEstimationGasLimitTooLowError` instead of decoding a (nonexistent)
+    /// revert stack trace. `OutOfGas` from any other method (e.g. a plain
+    /// `eth_sendTransaction` that simply ran out of the caller-supplied gas)
+    /// is left as an ordinary decoded stack trace.
+    gas_limit_too_low: Option<u64>,
     /// This may contain zero or more traces, depending on the (batch) request
     traces: Vec<Arc<edr_evm::trace::Trace>>,
 }
@@ -344,9 +502,27 @@ impl Response {
     }
 
     // Rust port of https://github.com/NomicFoundation/hardhat/blob/c20bf195a6efdc2d74e778b7a4a7799aac224841/packages/hardhat-core/src/internal/hardhat-network/provider/provider.ts#L590
+    //
+    // NOTE: this reconstructs the stack trace from whatever single trace
+    // `edr_provider` attached to the response, except for the
+    // `gas_limit_too_low` case below, which is built directly from the
+    // failure reason rather than decoded from a trace.
     #[doc = "Compute the error stack trace. Return the stack trace if it can be decoded, otherwise returns none. Throws if there was an error computing the stack trace."]
     #[napi]
     pub fn stack_trace(&self) -> napi::Result<Option<SolidityStackTrace>> {
+        if let Some(gas_limit) = self.gas_limit_too_low {
+            use crate::trace::solidity_stack_trace::{
+                GasEstimationGasLimitTooLowError, StackTraceEntryTypeConst,
+            };
+
+            return Ok(Some(vec![GasEstimationGasLimitTooLowError {
+                type_: StackTraceEntryTypeConst,
+                min_gas_limit: BigInt::from(gas_limit),
+                source_reference: None,
+            }
+            .into()]));
+        }
+
         let Some(SolidityTraceData {
             trace,
             contract_decoder,
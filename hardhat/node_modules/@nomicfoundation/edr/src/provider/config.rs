@@ -12,6 +12,9 @@ use napi::{
 };
 use napi_derive::napi;
 
+use super::retry::{
+    RetryPolicy, DEFAULT_INITIAL_DELAY_MS, DEFAULT_MAX_DELAY_MS, DEFAULT_MAX_RETRIES,
+};
 use crate::{account::GenesisAccount, block::BlobGas, cast::TryCast, config::SpecId};
 
 /// Configuration for a chain
@@ -28,13 +31,62 @@ pub struct ChainConfig {
 pub struct ForkConfig {
     /// The URL of the JSON-RPC endpoint to fork from
     pub json_rpc_url: String,
-    /// The block number to fork from. If not provided, the latest safe block is
-    /// used.
-    pub block_number: Option<BigInt>,
-    /// The HTTP headers to use when making requests to the JSON-RPC endpoint
+    /// The block to fork from: either a block number, or a block hash
+    /// (resolved via `eth_getBlockByHash`) for pinning to a reorg-stable
+    /// block. If not provided, the latest safe block is used.
+    pub block_number: Option<Either<BigInt, Buffer>>,
+    /// The HTTP headers to use when making requests to `json_rpc_url`
+    pub http_headers: Option<Vec<HttpHeader>>,
+    /// The retry policy to use for transient failures of the JSON-RPC
+    /// endpoint. If not provided, the default policy is used.
+    pub retry_config: Option<RetryConfig>,
+    /// Additional JSON-RPC endpoints to fall back to, in order, when
+    /// `json_rpc_url` keeps failing after `retry_config`'s retry budget is
+    /// exhausted. Each endpoint can specify its own `http_headers`, so mixed
+    /// auth setups (e.g. a paid primary provider and a free-tier fallback)
+    /// work.
+    pub fallback_urls: Option<Vec<ForkRpcEndpoint>>,
+}
+
+/// A JSON-RPC endpoint to fall back to when forking. See
+/// [`ForkConfig::fallback_urls`].
+#[napi(object)]
+pub struct ForkRpcEndpoint {
+    /// The URL of the JSON-RPC endpoint.
+    pub url: String,
+    /// The HTTP headers to use when making requests to this endpoint.
     pub http_headers: Option<Vec<HttpHeader>>,
 }
 
+/// Configuration of the retry policy applied to outbound JSON-RPC requests
+/// made while forking.
+#[napi(object)]
+pub struct RetryConfig {
+    /// The maximum number of times a transient failure (HTTP 429, 5xx,
+    /// connection resets, timeouts) will be retried. Deterministic JSON-RPC
+    /// errors are never retried.
+    pub max_retries: Option<u32>,
+    /// The delay, in milliseconds, before the first retry.
+    pub initial_delay_ms: Option<u32>,
+    /// The maximum delay, in milliseconds, between retries. The exponential
+    /// backoff is capped at this value before jitter is applied.
+    pub max_delay_ms: Option<u32>,
+}
+
+impl From<RetryConfig> for RetryPolicy {
+    fn from(value: RetryConfig) -> Self {
+        Self {
+            max_retries: value.max_retries.unwrap_or(DEFAULT_MAX_RETRIES),
+            initial_delay: Duration::from_millis(u64::from(
+                value.initial_delay_ms.unwrap_or(DEFAULT_INITIAL_DELAY_MS as u32),
+            )),
+            max_delay: Duration::from_millis(u64::from(
+                value.max_delay_ms.unwrap_or(DEFAULT_MAX_DELAY_MS as u32),
+            )),
+        }
+    }
+}
+
 #[napi(object)]
 pub struct HttpHeader {
     pub name: String,
@@ -127,22 +179,53 @@ pub struct ProviderConfig {
     pub network_id: BigInt,
 }
 
+/// Converts HTTP headers from their napi-facing representation into the
+/// `(name, value)` pairs the external crate expects.
+fn into_headers(http_headers: Vec<HttpHeader>) -> HashMap<String, String> {
+    http_headers
+        .into_iter()
+        .map(|HttpHeader { name, value }| (name, value))
+        .collect()
+}
+
 impl TryFrom<ForkConfig> for edr_provider::hardhat_rpc_types::ForkConfig {
     type Error = napi::Error;
 
     fn try_from(value: ForkConfig) -> Result<Self, Self::Error> {
-        let block_number: Option<u64> = value.block_number.map(TryCast::try_cast).transpose()?;
-        let http_headers = value.http_headers.map(|http_headers| {
-            http_headers
-                .into_iter()
-                .map(|HttpHeader { name, value }| (name, value))
-                .collect()
-        });
+        let (block_number, block_hash) = match value.block_number {
+            Some(Either::A(block_number)) => (Some(block_number.try_cast()?), None),
+            Some(Either::B(block_hash)) => (None, Some(block_hash.try_cast()?)),
+            None => (None, None),
+        };
+        let http_headers = value.http_headers.map(into_headers);
+        let retry_policy = RetryPolicy::from(value.retry_config.unwrap_or(RetryConfig {
+            max_retries: None,
+            initial_delay_ms: None,
+            max_delay_ms: None,
+        }));
+        let fallback_urls = value
+            .fallback_urls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|ForkRpcEndpoint { url, http_headers }| {
+                edr_provider::hardhat_rpc_types::ForkRpcEndpoint {
+                    url,
+                    http_headers: http_headers.map(into_headers),
+                }
+            })
+            .collect();
 
         Ok(Self {
             json_rpc_url: value.json_rpc_url,
             block_number,
+            block_hash,
             http_headers,
+            fallback_urls,
+            max_retries: retry_policy.max_retries,
+            initial_retry_delay_ms: u64::try_from(retry_policy.initial_delay.as_millis())
+                .unwrap_or(u64::MAX),
+            max_retry_delay_ms: u64::try_from(retry_policy.max_delay.as_millis())
+                .unwrap_or(u64::MAX),
         })
     }
 }
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// The default maximum number of retries for a transient forking RPC failure.
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+/// The default initial delay before the first retry.
+pub const DEFAULT_INITIAL_DELAY_MS: u64 = 250;
+/// The default cap on the exponential backoff delay.
+pub const DEFAULT_MAX_DELAY_MS: u64 = 10_000;
+
+/// The retry policy used when communicating with a forked JSON-RPC endpoint.
+///
+/// Delays follow an exponential backoff with jitter: `delay = min(max_delay,
+/// initial_delay * 2^attempt) ± up to 50%`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of retries before giving up.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub initial_delay: Duration,
+    /// The maximum delay between retries.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_delay: Duration::from_millis(DEFAULT_INITIAL_DELAY_MS),
+            max_delay: Duration::from_millis(DEFAULT_MAX_DELAY_MS),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay that should be waited before retrying the given
+    /// (zero-indexed) attempt, including jitter.
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .initial_delay
+            .saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX));
+        let capped = exponential.min(self.max_delay);
+
+        let jitter = rand::thread_rng().gen_range(0.5..=1.5);
+        capped.mul_f64(jitter)
+    }
+
+    /// Returns whether a request that failed on the given (zero-indexed)
+    /// attempt should be retried at all, based on the policy's retry budget
+    /// and the failure's classification.
+    pub fn should_retry(&self, attempt: u32, classification: FailureClassification) -> bool {
+        attempt < self.max_retries && classification.is_retryable()
+    }
+}
+
+/// The classification of a failed remote JSON-RPC request, used to decide
+/// whether it's safe to retry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureClassification {
+    /// A transient failure (rate limiting, connection reset, timeout, server
+    /// error) that is safe to retry.
+    Transient,
+    /// A deterministic failure (e.g. a well-formed JSON-RPC error response)
+    /// that would fail again if retried.
+    Deterministic,
+}
+
+impl FailureClassification {
+    /// Returns whether this classification should be retried.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Transient)
+    }
+
+    /// Classifies a transport-level failure based on the HTTP status code
+    /// returned by the remote endpoint, if any.
+    pub fn from_http_status(status: u16) -> Self {
+        if status == 429 || (500..600).contains(&status) {
+            Self::Transient
+        } else {
+            Self::Deterministic
+        }
+    }
+}
+
+/// The minimum and maximum (inclusive) semver-style node version that's
+/// considered compatible with this provider, per remote node type.
+#[derive(Clone, Debug)]
+pub struct SupportedVersionRange {
+    pub node_type: &'static str,
+    pub min_version: &'static str,
+    pub max_version: Option<&'static str>,
+}
+
+/// The ranges of remote node versions that are known to work well when
+/// forking. Versions outside of these ranges still work, but a warning is
+/// surfaced through the `Logger`.
+pub const KNOWN_COMPATIBLE_VERSIONS: &[SupportedVersionRange] = &[
+    SupportedVersionRange {
+        node_type: "Geth",
+        min_version: "1.10.0",
+        max_version: None,
+    },
+    SupportedVersionRange {
+        node_type: "Erigon",
+        min_version: "2.40.0",
+        max_version: None,
+    },
+];
+
+/// Parses a `web3_clientVersion` response (e.g. `"Geth/v1.13.5-stable/..."`)
+/// and, if the node type is one we have a known-compatible range for,
+/// returns a warning message when the reported version falls outside of it.
+///
+/// The actual `web3_clientVersion` handshake and retry transport are owned
+/// by the forking JSON-RPC client, which isn't part of this crate; this
+/// crate only translates [`RetryPolicy`]/[`ForkConfig`] into the numeric
+/// fields that client consumes. [`Provider::check_remote_node_version`] is
+/// the call site that client uses to run the response back through this
+/// check and get a warning to log.
+///
+/// [`ForkConfig`]: super::config::ForkConfig
+/// [`Provider::check_remote_node_version`]: super::Provider::check_remote_node_version
+pub fn check_node_version_compatibility(client_version: &str) -> Option<String> {
+    let mut parts = client_version.splitn(2, '/');
+    let node_type = parts.next()?;
+    let version = parts.next()?.trim_start_matches('v');
+
+    let range = KNOWN_COMPATIBLE_VERSIONS
+        .iter()
+        .find(|range| range.node_type.eq_ignore_ascii_case(node_type))?;
+
+    let is_too_old = compare_versions(version, range.min_version) == std::cmp::Ordering::Less;
+    let is_too_new = range.max_version.is_some_and(|max_version| {
+        compare_versions(version, max_version) == std::cmp::Ordering::Greater
+    });
+
+    if is_too_old || is_too_new {
+        Some(format!(
+            "The remote node reports `{client_version}`, which is outside of the known-compatible \
+             range for {} ({} <= version{}). Forking may behave unexpectedly.",
+            range.node_type,
+            range.min_version,
+            range
+                .max_version
+                .map_or(String::new(), |max_version| format!(" <= {max_version}"))
+        ))
+    } else {
+        None
+    }
+}
+
+/// Compares two `major.minor.patch`-style version strings numerically,
+/// component by component, rather than lexicographically: `"1.9.0" <
+/// "1.10.0"` must hold, even though it doesn't under ASCII/byte-string
+/// ordering (`'9' > '1'`). A missing or non-numeric component (e.g. a
+/// `-stable` pre-release suffix, or a shorter `major.minor`) is treated as
+/// `0`, so `"1.2"` and `"1.2.0"` compare equal.
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    fn components(version: &str) -> Vec<u64> {
+        version
+            .split(|c| c == '.' || c == '-')
+            .map(|component| component.parse().unwrap_or(0))
+            .collect()
+    }
+
+    let (a_components, b_components) = (components(a), components(b));
+    let len = a_components.len().max(b_components.len());
+
+    for index in 0..len {
+        let a_component = a_components.get(index).unwrap_or(&0);
+        let b_component = b_components.get(index).unwrap_or(&0);
+        let ordering = a_component.cmp(b_component);
+        if ordering != std::cmp::Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    std::cmp::Ordering::Equal
+}
@@ -61,3 +61,61 @@ pub struct SubscriptionEvent {
     pub filter_id: BigInt,
     pub result: serde_json::Value,
 }
+
+/// A single delivery of the historical block-range stream: either a block
+/// (with its matching logs already attached) or the final "caught up" marker.
+pub struct HistoricalStreamEvent {
+    /// The block's JSON-RPC representation, or `None` for the final
+    /// "caught up" marker.
+    pub block: Option<serde_json::Value>,
+    /// The logs emitted in this block that match the requested filter.
+    pub logs: Vec<serde_json::Value>,
+    /// Set once the stream has delivered every block in the requested range;
+    /// no further events follow. The consumer can switch over to the live
+    /// subscription at this point without gaps or duplicates.
+    pub caught_up: bool,
+}
+
+/// Threadsafe callback used to deliver a historical block-range stream,
+/// mirroring [`SubscriberCallback`] but used for a one-off backfill rather
+/// than a long-lived subscription.
+#[derive(Clone)]
+pub struct HistoricalStreamCallback {
+    inner: ThreadsafeFunction<HistoricalStreamEvent, ErrorStrategy::Fatal>,
+}
+
+impl HistoricalStreamCallback {
+    pub fn new(env: &Env, callback: JsFunction) -> napi::Result<Self> {
+        let mut callback = callback.create_threadsafe_function(
+            0,
+            |ctx: ThreadSafeCallContext<HistoricalStreamEvent>| {
+                let mut event = ctx.env.create_object()?;
+
+                let block = match ctx.value.block {
+                    Some(block) => ctx.env.to_js_value(&block)?,
+                    None => ctx.env.get_undefined()?.into_unknown(),
+                };
+                event.set_named_property("block", block)?;
+
+                let logs = ctx.env.to_js_value(&ctx.value.logs)?;
+                event.set_named_property("logs", logs)?;
+
+                event.set_named_property("caughtUp", ctx.env.get_boolean(ctx.value.caught_up)?)?;
+
+                Ok(vec![event])
+            },
+        )?;
+
+        // Maintain a weak reference to the function to avoid the event loop from
+        // exiting.
+        callback.unref(env)?;
+
+        Ok(Self { inner: callback })
+    }
+
+    pub fn call(&self, event: HistoricalStreamEvent) {
+        // Blocking because the caller relies on strictly ascending, non-duplicated
+        // delivery order.
+        self.inner.call(event, ThreadsafeFunctionCallMode::Blocking);
+    }
+}
@@ -1,11 +1,13 @@
 //! Naive rewrite of `hardhat-network/stack-traces/solidity-stack-traces.ts`
 //! from Hardhat.
 
+use std::collections::HashMap;
+
 use edr_eth::U256;
 use edr_evm::hex;
-use napi::bindgen_prelude::{BigInt, Either24, FromNapiValue, ToNapiValue, Uint8Array, Undefined};
+use napi::bindgen_prelude::{BigInt, Either26, FromNapiValue, ToNapiValue, Uint8Array, Undefined};
 use napi_derive::napi;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::model::ContractFunctionType;
 use crate::{cast::TryCast, trace::u256_to_bigint};
@@ -14,7 +16,9 @@ use crate::{cast::TryCast, trace::u256_to_bigint};
 #[repr(u8)]
 #[allow(non_camel_case_types)] // intentionally mimicks the original case in TS
 #[allow(clippy::upper_case_acronyms)]
-#[derive(PartialEq, Eq, PartialOrd, Ord, strum::FromRepr, strum::IntoStaticStr, Serialize)]
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, strum::FromRepr, strum::IntoStaticStr, Serialize, Deserialize,
+)]
 pub enum StackTraceEntryType {
     CALLSTACK_ENTRY = 0,
     UNRECOGNIZED_CREATE_CALLSTACK_ENTRY,
@@ -44,6 +48,13 @@ pub enum StackTraceEntryType {
     CONTRACT_TOO_LARGE_ERROR,
     INTERNAL_FUNCTION_CALLSTACK_ENTRY,
     CONTRACT_CALL_RUN_OUT_OF_GAS_ERROR,
+    // Specific to `eth_call`/`eth_estimateGas`: the call failed with a bare
+    // `OutOfGas` at the probed gas limit. Success at a higher limit is not
+    // checked; see [`GasEstimationGasLimitTooLowError`].
+    GAS_ESTIMATION_GAS_LIMIT_TOO_LOW_ERROR,
+    // A failing call whose target address is a recognized precompile; see
+    // [`PrecompileCallErrorStackTraceEntry`].
+    PRECOMPILE_CALL_ERROR,
 }
 
 #[napi]
@@ -67,7 +78,7 @@ pub const PRECOMPILE_FUNCTION_NAME: &str = "<precompile>";
 pub const UNRECOGNIZED_CONTRACT_NAME: &str = "<UnrecognizedContract>";
 
 #[napi(object)]
-#[derive(Clone, PartialEq, Serialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct SourceReference {
     pub source_name: String,
     pub source_content: String,
@@ -141,8 +152,26 @@ impl<const ENTRY_TYPE: u8> Serialize for StackTraceEntryTypeConst<ENTRY_TYPE> {
     }
 }
 
+impl<'de, const ENTRY_TYPE: u8> Deserialize<'de> for StackTraceEntryTypeConst<ENTRY_TYPE> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let inner = StackTraceEntryType::deserialize(deserializer)?;
+
+        if inner as u8 != ENTRY_TYPE {
+            return Err(serde::de::Error::custom(format!(
+                "Expected StackTraceEntryType value: {ENTRY_TYPE}, got: {}",
+                inner as u8
+            )));
+        }
+
+        Ok(StackTraceEntryTypeConst)
+    }
+}
+
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CallstackEntryStackTraceEntry {
     #[napi(js_name = "type", ts_type = "StackTraceEntryType.CALLSTACK_ENTRY")]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::CALLSTACK_ENTRY as u8 }>,
@@ -152,12 +181,12 @@ pub struct CallstackEntryStackTraceEntry {
 
 impl From<CallstackEntryStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: CallstackEntryStackTraceEntry) -> Self {
-        Either24::A(val)
+        Either26::A(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UnrecognizedCreateCallstackEntryStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -166,17 +195,18 @@ pub struct UnrecognizedCreateCallstackEntryStackTraceEntry {
     pub type_: StackTraceEntryTypeConst<
         { StackTraceEntryType::UNRECOGNIZED_CREATE_CALLSTACK_ENTRY as u8 },
     >,
+    #[serde(skip_deserializing, default)]
     pub source_reference: Option<Undefined>,
 }
 
 impl From<UnrecognizedCreateCallstackEntryStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: UnrecognizedCreateCallstackEntryStackTraceEntry) -> Self {
-        Either24::B(val)
+        Either26::B(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UnrecognizedContractCallstackEntryStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -185,38 +215,46 @@ pub struct UnrecognizedContractCallstackEntryStackTraceEntry {
     pub type_: StackTraceEntryTypeConst<
         { StackTraceEntryType::UNRECOGNIZED_CONTRACT_CALLSTACK_ENTRY as u8 },
     >,
-    #[serde(serialize_with = "serialize_uint8array_to_hex")]
+    #[serde(
+        serialize_with = "serialize_uint8array_to_hex",
+        deserialize_with = "deserialize_uint8array_from_hex"
+    )]
     pub address: Uint8Array,
+    #[serde(skip_deserializing, default)]
     pub source_reference: Option<Undefined>,
 }
 
 impl From<UnrecognizedContractCallstackEntryStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: UnrecognizedContractCallstackEntryStackTraceEntry) -> Self {
-        Either24::C(val)
+        Either26::C(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PrecompileErrorStackTraceEntry {
     #[napi(js_name = "type", ts_type = "StackTraceEntryType.PRECOMPILE_ERROR")]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::PRECOMPILE_ERROR as u8 }>,
     pub precompile: u32,
+    #[serde(skip_deserializing, default)]
     pub source_reference: Option<Undefined>,
 }
 
 impl From<PrecompileErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: PrecompileErrorStackTraceEntry) -> Self {
-        Either24::D(val)
+        Either26::D(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct RevertErrorStackTraceEntry {
     #[napi(js_name = "type", ts_type = "StackTraceEntryType.REVERT_ERROR")]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::REVERT_ERROR as u8 }>,
-    #[serde(serialize_with = "serialize_uint8array_to_hex")]
+    #[serde(
+        serialize_with = "serialize_uint8array_to_hex",
+        deserialize_with = "deserialize_uint8array_from_hex"
+    )]
     pub return_data: Uint8Array,
     pub source_reference: SourceReference,
     pub is_invalid_opcode_error: bool,
@@ -224,63 +262,331 @@ pub struct RevertErrorStackTraceEntry {
 
 impl From<RevertErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: RevertErrorStackTraceEntry) -> Self {
-        Either24::E(val)
+        Either26::E(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PanicErrorStackTraceEntry {
     #[napi(js_name = "type", ts_type = "StackTraceEntryType.PANIC_ERROR")]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::PANIC_ERROR as u8 }>,
-    #[serde(serialize_with = "serialize_evm_value_bigint_using_u256")]
+    #[serde(
+        serialize_with = "serialize_evm_value_bigint_using_u256",
+        deserialize_with = "deserialize_evm_value_bigint_using_u256"
+    )]
     pub error_code: BigInt,
+    /// A human-readable description of `error_code`, per the standard
+    /// `Panic(uint256)` code table. See [`panic_error_code_to_reason`].
+    pub reason: String,
     pub source_reference: Option<SourceReference>,
 }
 
 impl From<PanicErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: PanicErrorStackTraceEntry) -> Self {
-        Either24::F(val)
+        Either26::F(val)
+    }
+}
+
+/// Maps a Solidity `Panic(uint256)` error code to a human-readable
+/// description, per the standard code table emitted by the Solidity
+/// compiler.
+#[napi]
+pub fn panic_error_code_to_reason(code: BigInt) -> String {
+    let code = U256::from_limbs_slice(&code.words);
+
+    if code == U256::from(0x00u64) {
+        "generic compiler panic".to_string()
+    } else if code == U256::from(0x01u64) {
+        "assertion failed (`assert`)".to_string()
+    } else if code == U256::from(0x11u64) {
+        "arithmetic overflow/underflow".to_string()
+    } else if code == U256::from(0x12u64) {
+        "division or modulo by zero".to_string()
+    } else if code == U256::from(0x21u64) {
+        "invalid enum conversion".to_string()
+    } else if code == U256::from(0x22u64) {
+        "incorrectly encoded storage byte array".to_string()
+    } else if code == U256::from(0x31u64) {
+        "`.pop()` on empty array".to_string()
+    } else if code == U256::from(0x32u64) {
+        "array index out of bounds".to_string()
+    } else if code == U256::from(0x41u64) {
+        "excessive memory allocation / too large array".to_string()
+    } else if code == U256::from(0x51u64) {
+        "call to a zero-initialized internal function".to_string()
+    } else {
+        format!("unknown panic code {code:#x}")
     }
 }
 
+/// A decoded revert error, attached to
+/// [`UnrecognizedContractErrorStackTraceEntry`] and
+/// [`UnrecognizedCreateErrorStackTraceEntry`] when `return_data` matches a
+/// known selector.
+#[napi(object)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DecodedRevertError {
+    /// The matched error's name, e.g. `Error`, `Panic`, or a custom error's
+    /// signature as supplied via [`decode_revert_error`]'s registry.
+    pub name: String,
+    /// A human-readable rendering of the decoded argument(s).
+    pub args: String,
+}
+
+// The standard `Error(string)`/`Panic(uint256)` revert selectors.
+// See <https://docs.soliditylang.org/en/v0.8.26/control-structures.html#error-handling-assert-require-revert-and-exceptions>
+alloy_sol_types::sol! {
+    error Error(string);
+    error Panic(uint256);
+}
+
+/// Decodes `return_data` against the standard `Error(string)` and
+/// `Panic(uint256)` selectors. Returns `None` when neither matches, or when
+/// the matched selector's payload is malformed.
+fn decode_known_revert_error(return_data: &[u8]) -> Option<DecodedRevertError> {
+    use alloy_sol_types::SolError;
+
+    if let Ok(error) = Error::abi_decode(return_data, false) {
+        return Some(DecodedRevertError {
+            name: "Error".to_string(),
+            args: error._0,
+        });
+    }
+
+    if let Ok(panic) = Panic::abi_decode(return_data, false) {
+        let code = BigInt {
+            sign_bit: false,
+            words: panic._0.as_limbs().to_vec(),
+        };
+
+        return Some(DecodedRevertError {
+            name: "Panic".to_string(),
+            args: panic_error_code_to_reason(code),
+        });
+    }
+
+    None
+}
+
+/// Decodes `return_data` against the standard `Error(string)`/
+/// `Panic(uint256)` selectors, falling back to `custom_errors` (a
+/// caller-supplied registry mapping a lowercase hex-encoded 4-byte selector
+/// to the originating error's signature, e.g. `"Foo(uint256,address)"`) for
+/// any other selector. Returns `None` when nothing matches.
+///
+/// For a matched custom error, `args` is a human-readable rendering of the
+/// decoded argument(s), e.g. `"(100, 0x1234...)"`, built the same way as
+/// [`decode_custom_error_arguments`]. If the registered signature doesn't
+/// parse, or the payload doesn't decode against it, `args` falls back to the
+/// raw hex-encoded payload.
+#[napi]
+pub fn decode_revert_error(
+    return_data: Uint8Array,
+    custom_errors: HashMap<String, String>,
+) -> Option<DecodedRevertError> {
+    let return_data = return_data.as_ref();
+
+    if let Some(decoded) = decode_known_revert_error(return_data) {
+        return Some(decoded);
+    }
+
+    let selector = return_data.get(..4)?;
+    let signature = custom_errors.get(&hex::encode(selector))?;
+
+    let args = decode_custom_error_arguments(Uint8Array::from(return_data), vec![signature.clone()])
+        .ok()
+        .flatten()
+        .map(|args| {
+            format!(
+                "({})",
+                args.iter()
+                    .map(|arg| arg.value.clone())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+        .unwrap_or_else(|| hex::encode(return_data.get(4..).unwrap_or_default()));
+
+    Some(DecodedRevertError {
+        name: signature.clone(),
+        args,
+    })
+}
+
+/// A single custom-error argument, decoded from the revert `return_data`
+/// against the matching ABI error selector.
+#[napi(object)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DecodedErrorArgument {
+    /// The argument's name, as declared in the Solidity error definition.
+    /// `None` for unnamed arguments.
+    pub name: Option<String>,
+    pub solidity_type: String,
+    /// The argument's value, rendered the way Solidity would print it
+    /// (decimal integers, `0x`-prefixed hex for bytes).
+    pub value: String,
+}
+
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CustomErrorStackTraceEntry {
     #[napi(js_name = "type", ts_type = "StackTraceEntryType.CUSTOM_ERROR")]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::CUSTOM_ERROR as u8 }>,
     // unlike RevertErrorStackTraceEntry, this includes the message already parsed
     pub message: String,
+    /// The custom error's arguments, as decoded by
+    /// [`decode_custom_error_arguments`]. `None` when they were not decoded,
+    /// in which case only `message` describes the error.
+    ///
+    /// Always `None` on values produced by the
+    /// `edr_solidity::solidity_stack_trace::StackTraceEntry::CustomError`
+    /// conversion below: that variant only carries the already-formatted
+    /// `message`, not the raw `return_data`/selector that decoding needs.
+    /// This is an upstream gap in `edr_solidity`, not something this binding
+    /// layer can paper over. Callers that do have the raw revert data (e.g.
+    /// from the call's `return_data`) can populate this field themselves by
+    /// calling [`decode_custom_error_arguments`].
+    pub decoded_inputs: Option<Vec<DecodedErrorArgument>>,
     pub source_reference: SourceReference,
 }
 
 impl From<CustomErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: CustomErrorStackTraceEntry) -> Self {
-        Either24::G(val)
+        Either26::G(val)
+    }
+}
+
+/// Decodes a custom Solidity error's arguments from raw revert `return_data`
+/// against `abi_errors` (full human-readable error signatures, e.g.
+/// `"InsufficientBalance(uint256 available, uint256 required)"`), matching by
+/// 4-byte selector. Returns `None` when `return_data`'s selector doesn't
+/// match any of the supplied fragments.
+#[napi]
+pub fn decode_custom_error_arguments(
+    return_data: Uint8Array,
+    abi_errors: Vec<String>,
+) -> napi::Result<Option<Vec<DecodedErrorArgument>>> {
+    if return_data.len() < 4 {
+        return Ok(None);
+    }
+    let selector = &return_data[0..4];
+
+    for signature in abi_errors {
+        let error = alloy_json_abi::Error::parse(&signature).map_err(|err| {
+            napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Invalid error signature `{signature}`: {err}"),
+            )
+        })?;
+
+        if error.selector().0[..] != selector[..] {
+            continue;
+        }
+
+        let param_types = error
+            .inputs
+            .iter()
+            .map(|param| {
+                alloy_dyn_abi::DynSolType::parse(&param.ty).map_err(|err| {
+                    napi::Error::new(
+                        napi::Status::InvalidArg,
+                        format!("Invalid parameter type `{}`: {err}", param.ty),
+                    )
+                })
+            })
+            .collect::<napi::Result<Vec<_>>>()?;
+
+        let decoded = alloy_dyn_abi::DynSolType::Tuple(param_types)
+            .abi_decode_sequence(&return_data[4..])
+            .map_err(|err| {
+                napi::Error::new(
+                    napi::Status::InvalidArg,
+                    format!("Failed decoding `{signature}`: {err}"),
+                )
+            })?;
+
+        let alloy_dyn_abi::DynSolValue::Tuple(values) = decoded else {
+            unreachable!("decoding a Tuple type always yields a Tuple value")
+        };
+
+        let args = error
+            .inputs
+            .iter()
+            .zip(values.iter())
+            .map(|(param, value)| DecodedErrorArgument {
+                name: if param.name.is_empty() {
+                    None
+                } else {
+                    Some(param.name.clone())
+                },
+                solidity_type: param.ty.clone(),
+                value: format_decoded_error_value(value),
+            })
+            .collect();
+
+        return Ok(Some(args));
+    }
+
+    Ok(None)
+}
+
+/// Renders a decoded ABI value the way Solidity would print it.
+fn format_decoded_error_value(value: &alloy_dyn_abi::DynSolValue) -> String {
+    use alloy_dyn_abi::DynSolValue;
+
+    match value {
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::FixedBytes(value, size) => format!("0x{}", hex::encode(&value[..*size])),
+        DynSolValue::Address(value) => value.to_string(),
+        DynSolValue::Function(value) => format!("0x{}", hex::encode(value.as_slice())),
+        DynSolValue::Bytes(value) => format!("0x{}", hex::encode(value)),
+        DynSolValue::String(value) => value.clone(),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(format_decoded_error_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        DynSolValue::Tuple(values) | DynSolValue::CustomStruct { tuple: values, .. } => format!(
+            "({})",
+            values
+                .iter()
+                .map(format_decoded_error_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FunctionNotPayableErrorStackTraceEntry {
     #[napi(
         js_name = "type",
         ts_type = "StackTraceEntryType.FUNCTION_NOT_PAYABLE_ERROR"
     )]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::FUNCTION_NOT_PAYABLE_ERROR as u8 }>,
-    #[serde(serialize_with = "serialize_evm_value_bigint_using_u256")]
+    #[serde(
+        serialize_with = "serialize_evm_value_bigint_using_u256",
+        deserialize_with = "deserialize_evm_value_bigint_using_u256"
+    )]
     pub value: BigInt,
     pub source_reference: SourceReference,
 }
 
 impl From<FunctionNotPayableErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: FunctionNotPayableErrorStackTraceEntry) -> Self {
-        Either24::H(val)
+        Either26::H(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct InvalidParamsErrorStackTraceEntry {
     #[napi(js_name = "type", ts_type = "StackTraceEntryType.INVALID_PARAMS_ERROR")]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::INVALID_PARAMS_ERROR as u8 }>,
@@ -289,31 +595,34 @@ pub struct InvalidParamsErrorStackTraceEntry {
 
 impl From<InvalidParamsErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: InvalidParamsErrorStackTraceEntry) -> Self {
-        Either24::I(val)
+        Either26::I(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FallbackNotPayableErrorStackTraceEntry {
     #[napi(
         js_name = "type",
         ts_type = "StackTraceEntryType.FALLBACK_NOT_PAYABLE_ERROR"
     )]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::FALLBACK_NOT_PAYABLE_ERROR as u8 }>,
-    #[serde(serialize_with = "serialize_evm_value_bigint_using_u256")]
+    #[serde(
+        serialize_with = "serialize_evm_value_bigint_using_u256",
+        deserialize_with = "deserialize_evm_value_bigint_using_u256"
+    )]
     pub value: BigInt,
     pub source_reference: SourceReference,
 }
 
 impl From<FallbackNotPayableErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: FallbackNotPayableErrorStackTraceEntry) -> Self {
-        Either24::J(val)
+        Either26::J(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FallbackNotPayableAndNoReceiveErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -322,19 +631,22 @@ pub struct FallbackNotPayableAndNoReceiveErrorStackTraceEntry {
     pub type_: StackTraceEntryTypeConst<
         { StackTraceEntryType::FALLBACK_NOT_PAYABLE_AND_NO_RECEIVE_ERROR as u8 },
     >,
-    #[serde(serialize_with = "serialize_evm_value_bigint_using_u256")]
+    #[serde(
+        serialize_with = "serialize_evm_value_bigint_using_u256",
+        deserialize_with = "deserialize_evm_value_bigint_using_u256"
+    )]
     pub value: BigInt,
     pub source_reference: SourceReference,
 }
 
 impl From<FallbackNotPayableAndNoReceiveErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: FallbackNotPayableAndNoReceiveErrorStackTraceEntry) -> Self {
-        Either24::K(val)
+        Either26::K(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UnrecognizedFunctionWithoutFallbackErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -348,12 +660,12 @@ pub struct UnrecognizedFunctionWithoutFallbackErrorStackTraceEntry {
 
 impl From<UnrecognizedFunctionWithoutFallbackErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: UnrecognizedFunctionWithoutFallbackErrorStackTraceEntry) -> Self {
-        Either24::L(val)
+        Either26::L(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MissingFallbackOrReceiveErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -366,12 +678,12 @@ pub struct MissingFallbackOrReceiveErrorStackTraceEntry {
 
 impl From<MissingFallbackOrReceiveErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: MissingFallbackOrReceiveErrorStackTraceEntry) -> Self {
-        Either24::M(val)
+        Either26::M(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ReturndataSizeErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -383,12 +695,12 @@ pub struct ReturndataSizeErrorStackTraceEntry {
 
 impl From<ReturndataSizeErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: ReturndataSizeErrorStackTraceEntry) -> Self {
-        Either24::N(val)
+        Either26::N(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct NonContractAccountCalledErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -401,12 +713,12 @@ pub struct NonContractAccountCalledErrorStackTraceEntry {
 
 impl From<NonContractAccountCalledErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: NonContractAccountCalledErrorStackTraceEntry) -> Self {
-        Either24::O(val)
+        Either26::O(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CallFailedErrorStackTraceEntry {
     #[napi(js_name = "type", ts_type = "StackTraceEntryType.CALL_FAILED_ERROR")]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::CALL_FAILED_ERROR as u8 }>,
@@ -415,12 +727,12 @@ pub struct CallFailedErrorStackTraceEntry {
 
 impl From<CallFailedErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: CallFailedErrorStackTraceEntry) -> Self {
-        Either24::P(val)
+        Either26::P(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct DirectLibraryCallErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -432,54 +744,75 @@ pub struct DirectLibraryCallErrorStackTraceEntry {
 
 impl From<DirectLibraryCallErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: DirectLibraryCallErrorStackTraceEntry) -> Self {
-        Either24::Q(val)
+        Either26::Q(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UnrecognizedCreateErrorStackTraceEntry {
     #[napi(
         js_name = "type",
         ts_type = "StackTraceEntryType.UNRECOGNIZED_CREATE_ERROR"
     )]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::UNRECOGNIZED_CREATE_ERROR as u8 }>,
-    #[serde(serialize_with = "serialize_uint8array_to_hex")]
+    #[serde(
+        serialize_with = "serialize_uint8array_to_hex",
+        deserialize_with = "deserialize_uint8array_from_hex"
+    )]
     pub return_data: Uint8Array,
+    /// The decoded `return_data`, when it matched the standard
+    /// `Error(string)`/`Panic(uint256)` selectors. `None` otherwise; see
+    /// [`decode_revert_error`] for matching against a custom-error ABI
+    /// registry as well.
+    pub decoded_error: Option<DecodedRevertError>,
+    #[serde(skip_deserializing, default)]
     pub source_reference: Option<Undefined>,
     pub is_invalid_opcode_error: bool,
 }
 
 impl From<UnrecognizedCreateErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: UnrecognizedCreateErrorStackTraceEntry) -> Self {
-        Either24::R(val)
+        Either26::R(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UnrecognizedContractErrorStackTraceEntry {
     #[napi(
         js_name = "type",
         ts_type = "StackTraceEntryType.UNRECOGNIZED_CONTRACT_ERROR"
     )]
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::UNRECOGNIZED_CONTRACT_ERROR as u8 }>,
-    #[serde(serialize_with = "serialize_uint8array_to_hex")]
+    #[serde(
+        serialize_with = "serialize_uint8array_to_hex",
+        deserialize_with = "deserialize_uint8array_from_hex"
+    )]
     pub address: Uint8Array,
-    #[serde(serialize_with = "serialize_uint8array_to_hex")]
+    #[serde(
+        serialize_with = "serialize_uint8array_to_hex",
+        deserialize_with = "deserialize_uint8array_from_hex"
+    )]
     pub return_data: Uint8Array,
+    /// The decoded `return_data`, when it matched the standard
+    /// `Error(string)`/`Panic(uint256)` selectors. `None` otherwise; see
+    /// [`decode_revert_error`] for matching against a custom-error ABI
+    /// registry as well.
+    pub decoded_error: Option<DecodedRevertError>,
+    #[serde(skip_deserializing, default)]
     pub source_reference: Option<Undefined>,
     pub is_invalid_opcode_error: bool,
 }
 
 impl From<UnrecognizedContractErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: UnrecognizedContractErrorStackTraceEntry) -> Self {
-        Either24::S(val)
+        Either26::S(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OtherExecutionErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -491,12 +824,12 @@ pub struct OtherExecutionErrorStackTraceEntry {
 
 impl From<OtherExecutionErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: OtherExecutionErrorStackTraceEntry) -> Self {
-        Either24::T(val)
+        Either26::T(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UnmappedSolc063RevertErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -509,12 +842,12 @@ pub struct UnmappedSolc063RevertErrorStackTraceEntry {
 
 impl From<UnmappedSolc063RevertErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: UnmappedSolc063RevertErrorStackTraceEntry) -> Self {
-        Either24::U(val)
+        Either26::U(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ContractTooLargeErrorStackTraceEntry {
     #[napi(
         js_name = "type",
@@ -526,12 +859,12 @@ pub struct ContractTooLargeErrorStackTraceEntry {
 
 impl From<ContractTooLargeErrorStackTraceEntry> for SolidityStackTraceEntry {
     fn from(val: ContractTooLargeErrorStackTraceEntry) -> Self {
-        Either24::V(val)
+        Either26::V(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct InternalFunctionCallStackEntry {
     #[napi(
         js_name = "type",
@@ -545,12 +878,12 @@ pub struct InternalFunctionCallStackEntry {
 
 impl From<InternalFunctionCallStackEntry> for SolidityStackTraceEntry {
     fn from(val: InternalFunctionCallStackEntry) -> Self {
-        Either24::W(val)
+        Either26::W(val)
     }
 }
 
 #[napi(object)]
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ContractCallRunOutOfGasError {
     #[napi(
         js_name = "type",
@@ -563,7 +896,148 @@ pub struct ContractCallRunOutOfGasError {
 
 impl From<ContractCallRunOutOfGasError> for SolidityStackTraceEntry {
     fn from(val: ContractCallRunOutOfGasError) -> Self {
-        Either24::X(val)
+        Either26::X(val)
+    }
+}
+
+/// Emitted for `eth_call`/`eth_estimateGas` when a probed gas limit runs
+/// out of gas (`TransactionFailureReason::OutOfGas`), rather than the call
+/// genuinely reverting. Distinguishes this case from a genuine logic revert,
+/// which instead produces the usual revert stack trace.
+///
+/// `min_gas_limit` is the gas limit that was probed and found insufficient,
+/// not a limit verified to succeed: the search itself isn't vendored in this
+/// tree, so the binding layer can't re-run the call at a higher limit to
+/// confirm one. See `Response::stack_trace` in `provider.rs`.
+#[napi(object)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GasEstimationGasLimitTooLowError {
+    #[napi(
+        js_name = "type",
+        ts_type = "StackTraceEntryType.GAS_ESTIMATION_GAS_LIMIT_TOO_LOW_ERROR"
+    )]
+    pub type_: StackTraceEntryTypeConst<
+        { StackTraceEntryType::GAS_ESTIMATION_GAS_LIMIT_TOO_LOW_ERROR as u8 },
+    >,
+    #[serde(
+        serialize_with = "serialize_evm_value_bigint_using_u256",
+        deserialize_with = "deserialize_evm_value_bigint_using_u256"
+    )]
+    pub min_gas_limit: BigInt,
+    pub source_reference: Option<SourceReference>,
+}
+
+impl From<GasEstimationGasLimitTooLowError> for SolidityStackTraceEntry {
+    fn from(val: GasEstimationGasLimitTooLowError) -> Self {
+        Either26::Y(val)
+    }
+}
+
+/// The well-known Ethereum precompile addresses (`0x01`-`0x0a`) and their
+/// names, per the Yellow Paper and EIP-152/EIP-1108/EIP-2537.
+const STANDARD_PRECOMPILES: &[(u8, &str)] = &[
+    (0x01, "ecrecover"),
+    (0x02, "sha256"),
+    (0x03, "ripemd160"),
+    (0x04, "identity"),
+    (0x05, "modexp"),
+    (0x06, "bn256Add"),
+    (0x07, "bn256Mul"),
+    (0x08, "bn256Pairing"),
+    (0x09, "blake2f"),
+    (0x0a, "pointEvaluation"),
+];
+
+/// Looks up a standard precompile's name by its 20-byte address. Returns
+/// `None` for any address outside the `0x01`-`0x0a` range, including
+/// chain-specific precompiles; see [`precompile_name`] for those.
+fn standard_precompile_name(address: &[u8]) -> Option<&'static str> {
+    if address.len() != 20 || address[..19].iter().any(|&byte| byte != 0) {
+        return None;
+    }
+
+    STANDARD_PRECOMPILES
+        .iter()
+        .find(|(id, _)| *id == address[19])
+        .map(|(_, name)| *name)
+}
+
+/// Looks up a precompile's name by its 20-byte address: the standard
+/// `0x01`-`0x0a` range, falling back to `chain_precompiles` (a
+/// caller-supplied registry mapping a lowercase hex-encoded 20-byte address
+/// to a name) for chain-specific precompiles configured outside that range,
+/// as e.g. an L2 with its own precompile set would need.
+#[napi]
+pub fn precompile_name(
+    address: Uint8Array,
+    chain_precompiles: HashMap<String, String>,
+) -> Option<String> {
+    let address = address.as_ref();
+
+    if let Some(name) = standard_precompile_name(address) {
+        return Some(name.to_string());
+    }
+
+    chain_precompiles.get(&hex::encode(address)).cloned()
+}
+
+/// A best-effort, name-keyed guess at why a call to a standard precompile
+/// failed, based on the fact that it returned no data at all (the common
+/// signature of a malformed-input revert in the reference precompile
+/// implementations). Returns `None` when `return_data` isn't empty, or the
+/// precompile has no well-known failure mode to flag.
+fn likely_precompile_misuse(name: &str, return_data: &[u8]) -> Option<String> {
+    if !return_data.is_empty() {
+        return None;
+    }
+
+    match name {
+        "ecrecover" => Some(
+            "likely a malformed signature or wrong input length (expected 128 bytes: hash, v, r, s)"
+                .to_string(),
+        ),
+        "modexp" => Some(
+            "likely a malformed input (expected 3 32-byte length words followed by base/exponent/modulus)"
+                .to_string(),
+        ),
+        "bn256Add" | "bn256Mul" => {
+            Some("likely a point not on the curve, or wrong input length".to_string())
+        }
+        "bn256Pairing" => Some(
+            "likely a bad pairing check input: a point not on the curve, or an input length that isn't a multiple of 192 bytes"
+                .to_string(),
+        ),
+        "blake2f" => Some(
+            "likely a malformed input (expected 213 bytes, with the final byte being 0 or 1)"
+                .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+/// Emitted instead of [`UnrecognizedContractErrorStackTraceEntry`] when the
+/// failing call's target address is one of the standard `0x01`-`0x0a`
+/// precompiles, naming it and, when
+/// inferable, flagging a likely misuse (e.g. wrong input length).
+#[napi(object)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PrecompileCallErrorStackTraceEntry {
+    #[napi(js_name = "type", ts_type = "StackTraceEntryType.PRECOMPILE_CALL_ERROR")]
+    pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::PRECOMPILE_CALL_ERROR as u8 }>,
+    #[serde(
+        serialize_with = "serialize_uint8array_to_hex",
+        deserialize_with = "deserialize_uint8array_from_hex"
+    )]
+    pub address: Uint8Array,
+    pub precompile_name: String,
+    pub likely_misuse: Option<String>,
+    #[serde(skip_deserializing, default)]
+    pub source_reference: Option<Undefined>,
+}
+
+impl From<PrecompileCallErrorStackTraceEntry> for SolidityStackTraceEntry {
+    fn from(val: PrecompileCallErrorStackTraceEntry) -> Self {
+        Either26::Z(val)
     }
 }
 
@@ -572,12 +1046,12 @@ impl From<ContractCallRunOutOfGasError> for SolidityStackTraceEntry {
 // side of the bindings. However, napi-rs does not support exporting Rust type
 // aliases to the index.d.ts file, and it does not store the type definitions
 // when expanding the macros, so to use it we would have to specify this type
-// literally (all 26 lines of it) at every #[napi]-exported function, which is
+// literally (all 28 lines of it) at every #[napi]-exported function, which is
 // not ideal.
 // Rather, we just bite the bullet for now and use the type alias directly
 // (which falls back to `any` as it's not recognized in the context of the
 // index.d.ts file) until we finish the porting work.
-pub type SolidityStackTraceEntry = Either24<
+pub type SolidityStackTraceEntry = Either26<
     CallstackEntryStackTraceEntry,
     UnrecognizedCreateCallstackEntryStackTraceEntry,
     UnrecognizedContractCallstackEntryStackTraceEntry,
@@ -602,6 +1076,8 @@ pub type SolidityStackTraceEntry = Either24<
     ContractTooLargeErrorStackTraceEntry,
     InternalFunctionCallStackEntry,
     ContractCallRunOutOfGasError,
+    GasEstimationGasLimitTooLowError,
+    PrecompileCallErrorStackTraceEntry,
 >;
 
 impl TryCast<SolidityStackTraceEntry> for edr_solidity::solidity_stack_trace::StackTraceEntry {
@@ -654,18 +1130,23 @@ impl TryCast<SolidityStackTraceEntry> for edr_solidity::solidity_stack_trace::St
             StackTraceEntry::PanicError {
                 error_code,
                 source_reference,
-            } => PanicErrorStackTraceEntry {
-                type_: StackTraceEntryTypeConst,
-                error_code: u256_to_bigint(&error_code),
-                source_reference: source_reference.map(std::convert::Into::into),
+            } => {
+                let error_code = u256_to_bigint(&error_code);
+                PanicErrorStackTraceEntry {
+                    type_: StackTraceEntryTypeConst,
+                    reason: panic_error_code_to_reason(error_code.clone()),
+                    error_code,
+                    source_reference: source_reference.map(std::convert::Into::into),
+                }
+                .into()
             }
-            .into(),
             StackTraceEntry::CustomError {
                 message,
                 source_reference,
             } => CustomErrorStackTraceEntry {
                 type_: StackTraceEntryTypeConst,
                 message,
+                decoded_inputs: None,
                 source_reference: source_reference.into(),
             }
             .into(),
@@ -724,6 +1205,12 @@ impl TryCast<SolidityStackTraceEntry> for edr_solidity::solidity_stack_trace::St
                 }
                 .into()
             }
+            // NOTE: `edr_solidity::solidity_stack_trace::StackTraceEntry`'s
+            // `NoncontractAccountCalledError` variant doesn't carry the
+            // called address, so a [`PrecompileCallErrorStackTraceEntry`]
+            // can't be produced here; only `UnrecognizedContractError` below
+            // (which does carry an address) can be recognized as targeting
+            // a precompile.
             StackTraceEntry::NoncontractAccountCalledError { source_reference } => {
                 NonContractAccountCalledErrorStackTraceEntry {
                     type_: StackTraceEntryTypeConst,
@@ -731,6 +1218,9 @@ impl TryCast<SolidityStackTraceEntry> for edr_solidity::solidity_stack_trace::St
                 }
                 .into()
             }
+            // NOTE: same limitation as `NoncontractAccountCalledError` above:
+            // no address is available here to check against the precompile
+            // registry.
             StackTraceEntry::CallFailedError { source_reference } => {
                 CallFailedErrorStackTraceEntry {
                     type_: StackTraceEntryTypeConst,
@@ -750,6 +1240,7 @@ impl TryCast<SolidityStackTraceEntry> for edr_solidity::solidity_stack_trace::St
                 is_invalid_opcode_error,
             } => UnrecognizedCreateErrorStackTraceEntry {
                 type_: StackTraceEntryTypeConst,
+                decoded_error: decode_known_revert_error(&return_data),
                 return_data: return_data.into(),
                 is_invalid_opcode_error,
                 source_reference: None,
@@ -759,14 +1250,28 @@ impl TryCast<SolidityStackTraceEntry> for edr_solidity::solidity_stack_trace::St
                 address,
                 return_data,
                 is_invalid_opcode_error,
-            } => UnrecognizedContractErrorStackTraceEntry {
-                type_: StackTraceEntryTypeConst,
-                address: Uint8Array::from(address.as_slice()),
-                return_data: return_data.into(),
-                is_invalid_opcode_error,
-                source_reference: None,
+            } => {
+                if let Some(name) = standard_precompile_name(&address) {
+                    PrecompileCallErrorStackTraceEntry {
+                        type_: StackTraceEntryTypeConst,
+                        address: Uint8Array::from(address.as_slice()),
+                        precompile_name: name.to_string(),
+                        likely_misuse: likely_precompile_misuse(name, &return_data),
+                        source_reference: None,
+                    }
+                    .into()
+                } else {
+                    UnrecognizedContractErrorStackTraceEntry {
+                        type_: StackTraceEntryTypeConst,
+                        address: Uint8Array::from(address.as_slice()),
+                        decoded_error: decode_known_revert_error(&return_data),
+                        return_data: return_data.into(),
+                        is_invalid_opcode_error,
+                        source_reference: None,
+                    }
+                    .into()
+                }
             }
-            .into(),
             StackTraceEntry::OtherExecutionError { source_reference } => {
                 OtherExecutionErrorStackTraceEntry {
                     type_: StackTraceEntryTypeConst,
@@ -818,6 +1323,167 @@ const _: () = {
     assert_to_from_napi_value::<SolidityStackTraceEntry>();
 };
 
+/// A selector over a [`SolidityStackTrace`], inspired by Parity's
+/// `trace_filter`. All provided predicates must match for an entry to be
+/// kept; omitted predicates are treated as always matching.
+#[napi(object)]
+#[derive(Clone)]
+pub struct StackTraceFilter {
+    /// Keep only entries whose `type` is one of these.
+    pub types: Option<Vec<StackTraceEntryType>>,
+    /// Keep only entries whose `address` field (present on the
+    /// unrecognized-contract callstack/error variants) equals
+    /// `from_address` or `to_address`. Entries without an `address` field
+    /// are dropped if either is set.
+    pub from_address: Option<Uint8Array>,
+    pub to_address: Option<Uint8Array>,
+    /// Skip this many matching entries before collecting results.
+    pub after: Option<u32>,
+    /// Collect at most this many matching entries.
+    pub count: Option<u32>,
+}
+
+fn stack_trace_entry_type(entry: &SolidityStackTraceEntry) -> StackTraceEntryType {
+    match entry {
+        Either26::A(_) => StackTraceEntryType::CALLSTACK_ENTRY,
+        Either26::B(_) => StackTraceEntryType::UNRECOGNIZED_CREATE_CALLSTACK_ENTRY,
+        Either26::C(_) => StackTraceEntryType::UNRECOGNIZED_CONTRACT_CALLSTACK_ENTRY,
+        Either26::D(_) => StackTraceEntryType::PRECOMPILE_ERROR,
+        Either26::E(_) => StackTraceEntryType::REVERT_ERROR,
+        Either26::F(_) => StackTraceEntryType::PANIC_ERROR,
+        Either26::G(_) => StackTraceEntryType::CUSTOM_ERROR,
+        Either26::H(_) => StackTraceEntryType::FUNCTION_NOT_PAYABLE_ERROR,
+        Either26::I(_) => StackTraceEntryType::INVALID_PARAMS_ERROR,
+        Either26::J(_) => StackTraceEntryType::FALLBACK_NOT_PAYABLE_ERROR,
+        Either26::K(_) => StackTraceEntryType::FALLBACK_NOT_PAYABLE_AND_NO_RECEIVE_ERROR,
+        Either26::L(_) => StackTraceEntryType::UNRECOGNIZED_FUNCTION_WITHOUT_FALLBACK_ERROR,
+        Either26::M(_) => StackTraceEntryType::MISSING_FALLBACK_OR_RECEIVE_ERROR,
+        Either26::N(_) => StackTraceEntryType::RETURNDATA_SIZE_ERROR,
+        Either26::O(_) => StackTraceEntryType::NONCONTRACT_ACCOUNT_CALLED_ERROR,
+        Either26::P(_) => StackTraceEntryType::CALL_FAILED_ERROR,
+        Either26::Q(_) => StackTraceEntryType::DIRECT_LIBRARY_CALL_ERROR,
+        Either26::R(_) => StackTraceEntryType::UNRECOGNIZED_CREATE_ERROR,
+        Either26::S(_) => StackTraceEntryType::UNRECOGNIZED_CONTRACT_ERROR,
+        Either26::T(_) => StackTraceEntryType::OTHER_EXECUTION_ERROR,
+        Either26::U(_) => StackTraceEntryType::UNMAPPED_SOLC_0_6_3_REVERT_ERROR,
+        Either26::V(_) => StackTraceEntryType::CONTRACT_TOO_LARGE_ERROR,
+        Either26::W(_) => StackTraceEntryType::INTERNAL_FUNCTION_CALLSTACK_ENTRY,
+        Either26::X(_) => StackTraceEntryType::CONTRACT_CALL_RUN_OUT_OF_GAS_ERROR,
+        Either26::Y(_) => StackTraceEntryType::GAS_ESTIMATION_GAS_LIMIT_TOO_LOW_ERROR,
+        Either26::Z(_) => StackTraceEntryType::PRECOMPILE_CALL_ERROR,
+    }
+}
+
+fn stack_trace_entry_address(entry: &SolidityStackTraceEntry) -> Option<&Uint8Array> {
+    match entry {
+        Either26::C(entry) => Some(&entry.address),
+        Either26::S(entry) => Some(&entry.address),
+        Either26::Z(entry) => Some(&entry.address),
+        _ => None,
+    }
+}
+
+/// Filters and paginates a [`SolidityStackTrace`] per `filter`. See
+/// [`StackTraceFilter`] for the matching semantics.
+#[napi]
+pub fn filter_stack_trace(
+    entries: Vec<SolidityStackTraceEntry>,
+    filter: StackTraceFilter,
+) -> Vec<SolidityStackTraceEntry> {
+    let matching = entries.into_iter().filter(|entry| {
+        if let Some(types) = &filter.types {
+            if !types.contains(&stack_trace_entry_type(entry)) {
+                return false;
+            }
+        }
+
+        if filter.from_address.is_some() || filter.to_address.is_some() {
+            let Some(address) = stack_trace_entry_address(entry) else {
+                return false;
+            };
+
+            let matches_from = filter
+                .from_address
+                .as_ref()
+                .is_some_and(|from_address| from_address.as_ref() == address.as_ref());
+            let matches_to = filter
+                .to_address
+                .as_ref()
+                .is_some_and(|to_address| to_address.as_ref() == address.as_ref());
+
+            if !(matches_from || matches_to) {
+                return false;
+            }
+        }
+
+        true
+    });
+
+    let after = filter.after.unwrap_or(0) as usize;
+    let count = filter.count.map_or(usize::MAX, |count| count as usize);
+
+    matching.skip(after).take(count).collect()
+}
+
+/// Current schema version of the document produced by
+/// [`stack_trace_to_json`]. Bump this whenever a change to the entry
+/// variants or their fields isn't backwards compatible with documents
+/// already written to disk.
+#[napi]
+pub const SOLIDITY_STACK_TRACE_SCHEMA_VERSION: u32 = 1;
+
+/// The versioned, durable on-disk/wire representation of a
+/// [`SolidityStackTrace`], as produced by [`stack_trace_to_json`] and
+/// consumed by [`stack_trace_from_json`]. Each entry's `type` discriminant
+/// is preserved (via [`StackTraceEntryTypeConst`]'s `Serialize`/
+/// `Deserialize` impls) and `Uint8Array` address/return-data fields are
+/// hex-encoded via [`serialize_uint8array_to_hex`]/
+/// [`deserialize_uint8array_from_hex`].
+#[derive(Serialize, Deserialize)]
+struct SolidityStackTraceDocument {
+    schema_version: u32,
+    entries: Vec<SolidityStackTraceEntry>,
+}
+
+/// Serializes a full [`SolidityStackTrace`] into a canonical, versioned JSON
+/// string. Intended for caching, snapshot-testing, and cross-process
+/// transport of traces.
+#[napi]
+pub fn stack_trace_to_json(entries: Vec<SolidityStackTraceEntry>) -> napi::Result<String> {
+    let document = SolidityStackTraceDocument {
+        schema_version: SOLIDITY_STACK_TRACE_SCHEMA_VERSION,
+        entries,
+    };
+
+    serde_json::to_string(&document)
+        .map_err(|error| napi::Error::new(napi::Status::GenericFailure, error.to_string()))
+}
+
+/// The inverse of [`stack_trace_to_json`]: reconstructs a
+/// [`SolidityStackTrace`] by dispatching on each entry's `type` discriminant,
+/// relying on `Either26` trying every variant's `Deserialize` impl in turn
+/// and [`StackTraceEntryTypeConst`] rejecting variants whose discriminant
+/// doesn't match. Rejects documents whose `schema_version` doesn't match
+/// [`SOLIDITY_STACK_TRACE_SCHEMA_VERSION`]; there is no migration support
+/// yet.
+#[napi]
+pub fn stack_trace_from_json(json: String) -> napi::Result<Vec<SolidityStackTraceEntry>> {
+    let document: SolidityStackTraceDocument = serde_json::from_str(&json)
+        .map_err(|error| napi::Error::new(napi::Status::GenericFailure, error.to_string()))?;
+
+    if document.schema_version != SOLIDITY_STACK_TRACE_SCHEMA_VERSION {
+        return Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            format!(
+                "Unsupported stack trace schema version: {}, expected: {}",
+                document.schema_version, SOLIDITY_STACK_TRACE_SCHEMA_VERSION
+            ),
+        ));
+    }
+
+    Ok(document.entries)
+}
+
 /// Serializes a [`BigInt`] that represents an EVM value as a [`edr_eth::U256`].
 fn serialize_evm_value_bigint_using_u256<S>(bigint: &BigInt, s: S) -> Result<S::Ok, S::Error>
 where
@@ -828,6 +1494,16 @@ where
     val.serialize(s)
 }
 
+/// The inverse of [`serialize_evm_value_bigint_using_u256`].
+fn deserialize_evm_value_bigint_using_u256<'de, D>(deserializer: D) -> Result<BigInt, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let val = U256::deserialize(deserializer)?;
+
+    Ok(BigInt::from(val))
+}
+
 fn serialize_uint8array_to_hex<S>(uint8array: &Uint8Array, s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -836,3 +1512,14 @@ where
 
     hex.serialize(s)
 }
+
+/// The inverse of [`serialize_uint8array_to_hex`].
+fn deserialize_uint8array_from_hex<'de, D>(deserializer: D) -> Result<Uint8Array, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let hex = String::deserialize(deserializer)?;
+    let bytes = hex::decode(hex).map_err(serde::de::Error::custom)?;
+
+    Ok(Uint8Array::from(bytes))
+}
@@ -1,18 +1,165 @@
 mod mina;
 
 use std::array::TryFromSliceError;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
 
+use anyhow::{anyhow, Error};
 use binprot::BinProtRead;
 use mina::{full_transaction_commitment, HashParameter, Message};
 use mina_p2p_messages::v2::{
     MinaBaseUserCommandStableV2, MinaBaseZkappCommandTStableV1WireStableV1,
 };
-use mina_signer::{BaseField, CurvePoint, PubKey, ScalarField, Signature};
+use mina_signer::{BaseField, CurvePoint, Keypair, PubKey, ScalarField, Signature};
 use mina_tree::scan_state::transaction_logic::zkapp_command::ZkAppCommand;
 use o1_utils::FieldHelpers;
+use serde_derive::{Deserialize, Serialize};
 
 pub const FIELD_SIZE: usize = 32;
 
+/// Converts a byte buffer into a NUL-terminated C string, for returning a
+/// JSON result across the FFI boundary.
+fn vec_to_c_char(bytes: Vec<u8>) -> *const c_char {
+    CString::new(bytes)
+        .expect("fail to create new CString from bytes")
+        .into_raw()
+}
+
+fn network_id_from_byte(network_id: u8) -> Result<HashParameter, Error> {
+    match network_id {
+        0x00 => Ok(HashParameter::Mainnet),
+        0x01 => Ok(HashParameter::Testnet),
+        0x02 => Ok(HashParameter::Empty),
+        0x03 => Ok(HashParameter::TransactionCommitment),
+        _ => Err(anyhow!("invalid network id: {network_id}")),
+    }
+}
+
+fn fields_from_hex(fields: &[String]) -> Result<Message, Error> {
+    let fields = fields
+        .iter()
+        .map(|field| BaseField::from_hex(field))
+        .collect::<Result<Vec<BaseField>, _>>()
+        .map_err(|error| anyhow!("invalid field limb: {error:?}"))?;
+
+    Ok(Message { fields })
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignRequest {
+    pub network_id: u8,
+    /// Hex-encoded secret key.
+    pub secret_key: String,
+    /// Hex-encoded field limbs to sign.
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SignResult {
+    pub signature_rx: Option<String>,
+    pub signature_s: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Signs `fields` with `secret_key`, producing a Mina-style signature.
+///
+/// Takes a JSON-encoded [`SignRequest`] and returns a JSON-encoded
+/// [`SignResult`] via [`vec_to_c_char`], mirroring the `CommonResult`/
+/// error-string convention used by the circuit capacity checker's FFI.
+///
+/// # Safety
+/// this function accepts a raw pointer from golang
+#[no_mangle]
+pub unsafe extern "C" fn sign(payload: *const c_char) -> *const c_char {
+    let result = sign_inner(payload);
+    let r = match result {
+        Ok(signature) => SignResult {
+            signature_rx: Some(signature.rx.to_hex()),
+            signature_s: Some(signature.s.to_hex()),
+            error: None,
+        },
+        Err(e) => SignResult {
+            signature_rx: None,
+            signature_s: None,
+            error: Some(format!("{e:?}")),
+        },
+    };
+    serde_json::to_vec(&r).map_or(std::ptr::null(), vec_to_c_char)
+}
+
+unsafe fn sign_inner(payload: *const c_char) -> Result<Signature, Error> {
+    let payload = CStr::from_ptr(payload).to_bytes();
+    let request: SignRequest = serde_json::from_slice(payload)?;
+
+    let network_id = network_id_from_byte(request.network_id)?;
+    let keypair = Keypair::from_hex(&request.secret_key)
+        .map_err(|error| anyhow!("invalid secret key: {error:?}"))?;
+    let msg = fields_from_hex(&request.fields)?;
+
+    Ok(mina::sign(&keypair, &msg, network_id))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VerifyRequest {
+    pub network_id: u8,
+    pub pub_key_x: String,
+    pub pub_key_y: String,
+    pub sig_rx: String,
+    pub sig_s: String,
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyResult {
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// Verifies a Mina-style signature against a JSON-encoded [`VerifyRequest`],
+/// returning a JSON-encoded [`VerifyResult`]. This complements [`verify`],
+/// which takes its arguments as raw field-element pointers instead.
+///
+/// # Safety
+/// this function accepts a raw pointer from golang
+#[no_mangle]
+pub unsafe extern "C" fn verify_json(payload: *const c_char) -> *const c_char {
+    let result = verify_json_inner(payload);
+    let r = match result {
+        Ok(valid) => VerifyResult { valid, error: None },
+        Err(e) => VerifyResult {
+            valid: false,
+            error: Some(format!("{e:?}")),
+        },
+    };
+    serde_json::to_vec(&r).map_or(std::ptr::null(), vec_to_c_char)
+}
+
+unsafe fn verify_json_inner(payload: *const c_char) -> Result<bool, Error> {
+    let payload = CStr::from_ptr(payload).to_bytes();
+    let request: VerifyRequest = serde_json::from_slice(payload)?;
+
+    let network_id = network_id_from_byte(request.network_id)?;
+
+    let pubkey = PubKey::from_point_unsafe(CurvePoint::new(
+        BaseField::from_hex(&request.pub_key_x)
+            .map_err(|error| anyhow!("invalid pub_key_x: {error:?}"))?,
+        BaseField::from_hex(&request.pub_key_y)
+            .map_err(|error| anyhow!("invalid pub_key_y: {error:?}"))?,
+        false,
+    ));
+
+    let signature = Signature::new(
+        BaseField::from_hex(&request.sig_rx)
+            .map_err(|error| anyhow!("invalid sig_rx: {error:?}"))?,
+        ScalarField::from_hex(&request.sig_s)
+            .map_err(|error| anyhow!("invalid sig_s: {error:?}"))?,
+    );
+
+    let msg = fields_from_hex(&request.fields)?;
+
+    Ok(mina::verify(&signature, &pubkey, &msg, network_id))
+}
+
 /**
  * # Safety
  * this functions accepts raw pointer from golang
@@ -361,4 +508,77 @@ mod tests {
 
         assert_eq!(BaseField::from_bytes(&output).unwrap(), expected_commitment);
     }
+
+    /// Calls the `sign`/`verify_json` FFI entry points with a hand-built JSON
+    /// payload, reading the result back out of the C string they return.
+    unsafe fn call_json_ffi(
+        f: unsafe extern "C" fn(*const c_char) -> *const c_char,
+        payload: &serde_json::Value,
+    ) -> serde_json::Value {
+        let payload = CString::new(payload.to_string()).unwrap();
+        let output_ptr = f(payload.as_ptr());
+        let output = CStr::from_ptr(output_ptr).to_str().unwrap();
+
+        serde_json::from_str(output).unwrap()
+    }
+
+    #[test]
+    fn test_sign_and_verify_json_round_trip() {
+        let secret_key = ScalarField::from(7u64).to_hex();
+        let field = BaseField::from(123u64).to_hex();
+
+        let sign_result = unsafe {
+            call_json_ffi(
+                sign,
+                &serde_json::json!({
+                    "network_id": 0x01,
+                    "secret_key": secret_key,
+                    "fields": [field],
+                }),
+            )
+        };
+
+        assert!(sign_result["error"].is_null(), "{sign_result}");
+        let sig_rx = sign_result["signature_rx"].as_str().unwrap();
+        let sig_s = sign_result["signature_s"].as_str().unwrap();
+
+        let keypair = Keypair::from_hex(&secret_key).expect("valid secret key");
+        let public = keypair.public.point();
+
+        let verify_result = unsafe {
+            call_json_ffi(
+                verify_json,
+                &serde_json::json!({
+                    "network_id": 0x01,
+                    "pub_key_x": public.x.to_hex(),
+                    "pub_key_y": public.y.to_hex(),
+                    "sig_rx": sig_rx,
+                    "sig_s": sig_s,
+                    "fields": [field],
+                }),
+            )
+        };
+
+        assert!(verify_result["error"].is_null(), "{verify_result}");
+        assert_eq!(verify_result["valid"], true);
+    }
+
+    #[test]
+    fn test_sign_and_verify_json_malformed_input() {
+        let sign_result =
+            unsafe { call_json_ffi(sign, &serde_json::json!({ "not": "a sign request" })) };
+
+        assert!(sign_result["signature_rx"].is_null());
+        assert!(sign_result["error"].is_string());
+
+        let verify_result = unsafe {
+            call_json_ffi(
+                verify_json,
+                &serde_json::json!({ "not": "a verify request" }),
+            )
+        };
+
+        assert_eq!(verify_result["valid"], false);
+        assert!(verify_result["error"].is_string());
+    }
 }
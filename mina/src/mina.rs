@@ -1,5 +1,5 @@
 use mina_hasher::{DomainParameter, Hashable, Hasher, ROInput};
-use mina_signer::{BaseField, PubKey, Signature, Signer};
+use mina_signer::{BaseField, Keypair, PubKey, Signature, Signer};
 use o1_utils::{field_helpers::FieldHelpersError, FieldHelpers};
 
 #[derive(Debug, Clone)]
@@ -74,3 +74,9 @@ pub fn verify(
 
     signer.verify(signature, pubkey, msg)
 }
+
+pub fn sign(keypair: &Keypair, msg: &Message, network_id: HashParameter) -> Signature {
+    let mut signer = mina_signer::create_kimchi::<Message>(network_id);
+
+    signer.sign(keypair, msg)
+}
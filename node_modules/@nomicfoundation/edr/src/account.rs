@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+
 use edr_eth::signature::secret_key_from_str;
-use napi::{bindgen_prelude::BigInt, Status};
+use napi::{
+    bindgen_prelude::{BigInt, Buffer},
+    Status,
+};
 use napi_derive::napi;
 
 use crate::cast::TryCast;
@@ -26,3 +31,64 @@ impl TryFrom<GenesisAccount> for edr_provider::AccountConfig {
         })
     }
 }
+
+/// A full account snapshot to pre-seed at genesis, keyed by address rather
+/// than secret key. This mirrors a geth-style genesis `alloc` entry and lets
+/// callers start tests from arbitrary deployed state (contracts, storage)
+/// rather than only externally-owned accounts.
+#[napi(object)]
+pub struct GenesisAccountState {
+    /// The address of the account.
+    pub address: Buffer,
+    /// Account balance
+    pub balance: BigInt,
+    /// Account nonce
+    pub nonce: Option<BigInt>,
+    /// The account's contract code, if it's a contract account.
+    pub code: Option<Buffer>,
+    /// The account's storage, keyed and valued by 32-byte hex strings.
+    pub storage: Option<HashMap<String, String>>,
+}
+
+impl TryFrom<GenesisAccountState> for (edr_eth::Address, edr_provider::AccountOverride) {
+    type Error = napi::Error;
+
+    fn try_from(value: GenesisAccountState) -> Result<Self, Self::Error> {
+        let storage = value
+            .storage
+            .map(|storage| {
+                storage
+                    .into_iter()
+                    .map(|(slot, value)| {
+                        let slot: edr_eth::U256 = slot.parse().map_err(|_error| {
+                            napi::Error::new(
+                                Status::InvalidArg,
+                                format!("Invalid storage slot: {slot}"),
+                            )
+                        })?;
+                        let value: edr_eth::U256 = value.parse().map_err(|_error| {
+                            napi::Error::new(
+                                Status::InvalidArg,
+                                format!("Invalid storage value: {value}"),
+                            )
+                        })?;
+
+                        Ok((slot, value))
+                    })
+                    .collect::<Result<_, napi::Error>>()
+            })
+            .transpose()?;
+
+        let address = value.address.try_cast()?;
+        let account_override = edr_provider::AccountOverride {
+            balance: Some(value.balance.try_cast()?),
+            nonce: value.nonce.map(TryCast::try_cast).transpose()?,
+            code: value
+                .code
+                .map(|code| edr_eth::Bytes::copy_from_slice(&code)),
+            storage,
+        };
+
+        Ok((address, account_override))
+    }
+}
@@ -1,9 +1,21 @@
 use edr_eth::{Address, Bytes, B256, B64};
-use napi::bindgen_prelude::{BigInt, Buffer};
+use napi::{
+    bindgen_prelude::{BigInt, Buffer},
+    Status,
+};
 use napi_derive::napi;
+use sha2::{Digest, Sha256};
 
 use crate::{cast::TryCast, withdrawal::Withdrawal};
 
+/// The size, in bytes, of a single EIP-4844 blob (4096 field elements of 32
+/// bytes each).
+pub const BYTES_PER_BLOB: usize = 131_072;
+/// The gas charged for a single blob, per EIP-4844.
+pub const GAS_PER_BLOB: u64 = 131_072;
+/// The version byte prepended to a blob's versioned hash, per EIP-4844.
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
 #[napi(object)]
 pub struct BlockOptions {
     /// The parent block's hash
@@ -35,6 +47,11 @@ pub struct BlockOptions {
     /// The hash tree root of the parent beacon block for the given execution
     /// block (EIP-4788).
     pub parent_beacon_block_root: Option<Buffer>,
+    /// The blob sidecars of the block's type-3 transactions, one per
+    /// transaction, in transaction order. When provided, each sidecar's
+    /// shape and KZG proofs are verified, and their total blob count is
+    /// checked against `blob_gas.gas_used`.
+    pub blob_sidecars: Option<Vec<BlobSidecar>>,
 }
 
 impl TryFrom<BlockOptions> for edr_eth::block::BlockOptions {
@@ -42,6 +59,12 @@ impl TryFrom<BlockOptions> for edr_eth::block::BlockOptions {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn try_from(value: BlockOptions) -> Result<Self, Self::Error> {
+        let blob_gas = value
+            .blob_gas
+            .map(edr_eth::block::BlobGas::try_from)
+            .transpose()?;
+        verify_block_blob_sidecars(value.blob_sidecars.as_deref(), blob_gas.as_ref())?;
+
         Ok(Self {
             parent_hash: value
                 .parent_hash
@@ -84,10 +107,7 @@ impl TryFrom<BlockOptions> for edr_eth::block::BlockOptions {
                         .collect()
                 })
                 .transpose()?,
-            blob_gas: value
-                .blob_gas
-                .map(edr_eth::block::BlobGas::try_from)
-                .transpose()?,
+            blob_gas,
             parent_beacon_block_root: value
                 .parent_beacon_block_root
                 .map(TryCast::<B256>::try_cast)
@@ -96,6 +116,43 @@ impl TryFrom<BlockOptions> for edr_eth::block::BlockOptions {
     }
 }
 
+/// Verifies the blob sidecars of a block's type-3 transactions: every
+/// sidecar's shape and KZG proofs, and that the block's total blob count
+/// matches `blob_gas.gas_used`. Called as part of the `BlockOptions`
+/// conversion so that a block can't be built with unverified blob data.
+fn verify_block_blob_sidecars(
+    blob_sidecars: Option<&[BlobSidecar]>,
+    blob_gas: Option<&edr_eth::block::BlobGas>,
+) -> napi::Result<()> {
+    let Some(blob_sidecars) = blob_sidecars else {
+        return Ok(());
+    };
+
+    for sidecar in blob_sidecars {
+        sidecar.verify_proofs()?;
+    }
+
+    if let Some(blob_gas) = blob_gas {
+        let total_blobs: u64 = blob_sidecars
+            .iter()
+            .map(|sidecar| sidecar.blobs.len() as u64)
+            .sum();
+        let expected = total_blobs * GAS_PER_BLOB;
+
+        if blob_gas.gas_used != expected {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!(
+                    "Expected blob_gas.gas_used to be {expected} for {total_blobs} blob(s) across the block's sidecars, got {}",
+                    blob_gas.gas_used
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 /// Information about the blob gas used in a block.
 #[napi(object)]
 pub struct BlobGas {
@@ -119,3 +176,100 @@ impl TryFrom<BlobGas> for edr_eth::block::BlobGas {
         })
     }
 }
+
+/// The sidecar of blob data, commitments and proofs that accompanies a
+/// type-3 (EIP-4844) transaction. This is the same shape the consensus layer
+/// hands execution clients via the Engine API's `get_blobs_bundle`.
+#[napi(object)]
+pub struct BlobSidecar {
+    /// The blobs, each `BYTES_PER_BLOB` (131072) bytes long.
+    pub blobs: Vec<Buffer>,
+    /// The KZG commitment for each blob.
+    pub commitments: Vec<Buffer>,
+    /// The KZG proof for each blob.
+    pub proofs: Vec<Buffer>,
+}
+
+impl BlobSidecar {
+    /// Validates that `blobs`, `commitments` and `proofs` have matching
+    /// lengths and that every blob is exactly `BYTES_PER_BLOB` bytes long.
+    pub fn validate_shape(&self) -> napi::Result<()> {
+        if self.blobs.len() != self.commitments.len() || self.blobs.len() != self.proofs.len() {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                "The number of blobs, commitments and proofs must match".to_string(),
+            ));
+        }
+
+        if let Some(blob) = self.blobs.iter().find(|blob| blob.len() != BYTES_PER_BLOB) {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!(
+                    "Expected a blob of {BYTES_PER_BLOB} bytes, got {} bytes",
+                    blob.len()
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the sidecar's total blob gas usage matches
+    /// `blob_gas.gas_used`, per `blobs.length * GAS_PER_BLOB`.
+    pub fn validate_gas_used(&self, gas_used: u64) -> napi::Result<()> {
+        let expected = self.blobs.len() as u64 * GAS_PER_BLOB;
+        if gas_used != expected {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!(
+                    "Expected blob_gas.gas_used to be {expected} for {} blob(s), got {gas_used}",
+                    self.blobs.len()
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies every blob's KZG proof against its commitment, via a
+    /// c-kzg-style trusted-setup verifier.
+    pub fn verify_proofs(&self) -> napi::Result<()> {
+        self.validate_shape()?;
+
+        for ((blob, commitment), proof) in self
+            .blobs
+            .iter()
+            .zip(self.commitments.iter())
+            .zip(self.proofs.iter())
+        {
+            if !edr_eth::kzg::verify_blob_kzg_proof(blob.as_ref(), commitment.as_ref(), proof.as_ref())
+                .map_err(|error| napi::Error::new(Status::InvalidArg, error.to_string()))?
+            {
+                return Err(napi::Error::new(
+                    Status::InvalidArg,
+                    "KZG proof verification failed".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Verifies a blob sidecar's shape and KZG proofs, returning an error if
+/// either check fails. Exposed so a sidecar can be validated independently
+/// of building a block, e.g. before gossiping it.
+#[napi]
+pub fn verify_blob_sidecar(sidecar: BlobSidecar) -> napi::Result<()> {
+    sidecar.verify_proofs()
+}
+
+/// Computes the EIP-4844 versioned hash of a KZG commitment:
+/// `0x01 || sha256(commitment)[1..32]`.
+#[napi]
+pub fn compute_versioned_hash(commitment: Buffer) -> Buffer {
+    let mut hash = Sha256::digest(commitment.as_ref());
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+
+    Buffer::from(hash.as_slice())
+}
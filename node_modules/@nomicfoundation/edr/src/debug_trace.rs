@@ -1,8 +1,45 @@
 use std::collections::HashMap;
 
+use edr_evm::{interpreter::OpCode, trace::TraceMessage};
 use napi::bindgen_prelude::{BigInt, Buffer};
 use napi_derive::napi;
 
+use crate::trace::{fold_call_tree, CallTreeNode};
+
+/// The built-in tracer to use for a `debug_traceTransaction`/`debug_traceCall`
+/// request. When not provided, the default opcode-level struct logger is
+/// used.
+#[napi(string_enum)]
+pub enum DebugTraceTracer {
+    #[doc = "A recursive call tree, mirroring geth's `callTracer`."]
+    CallTracer,
+    #[doc = "The pre- and post-state of every account touched by the transaction. Not supported: `DebugTraceOutput::new` returns an error for it."]
+    PrestateTracer,
+    #[doc = "A histogram of `selector-calldatalen => count`."]
+    FourByteTracer,
+}
+
+/// Tracer configuration for a `debug_traceTransaction`/`debug_traceCall`
+/// request, threaded through to [`DebugTraceOutput::new`].
+#[napi(object)]
+pub struct DebugTraceConfig {
+    /// Selects a built-in tracer. When not provided, the default
+    /// opcode-level struct logger is used.
+    pub tracer: Option<DebugTraceTracer>,
+    /// Include the full memory array in each struct log entry. Ignored by
+    /// the built-in tracers. Defaults to `false`.
+    pub enable_memory: Option<bool>,
+    /// Omit the stack from each struct log entry. Ignored by the built-in
+    /// tracers. Defaults to `false`.
+    pub disable_stack: Option<bool>,
+    /// Omit the storage diff from each struct log entry. Ignored by the
+    /// built-in tracers. Defaults to `false`.
+    pub disable_storage: Option<bool>,
+    /// Caps the number of struct log entries returned. Ignored by the
+    /// built-in tracers.
+    pub limit: Option<u32>,
+}
+
 #[napi(object)]
 pub struct DebugTraceResult {
     pub pass: bool,
@@ -11,6 +48,196 @@ pub struct DebugTraceResult {
     pub struct_logs: Vec<DebugTraceLogItem>,
 }
 
+/// A single node of a `callTracer` call tree.
+#[napi(object)]
+pub struct CallTracerCall {
+    /// `CALL`, `STATICCALL`, `DELEGATECALL` or `CREATE`, as reported by
+    /// [`CallTreeNode::call_type`]; `CREATE2` is reported as `CREATE`
+    #[napi(js_name = "type")]
+    pub call_type: String,
+    pub from: Buffer,
+    pub to: Option<Buffer>,
+    pub value: Option<BigInt>,
+    pub gas: BigInt,
+    pub gas_used: BigInt,
+    pub input: Buffer,
+    pub output: Option<Buffer>,
+    pub error: Option<String>,
+    pub calls: Vec<CallTracerCall>,
+}
+
+impl From<CallTreeNode> for CallTracerCall {
+    fn from(node: CallTreeNode) -> Self {
+        Self {
+            call_type: node.call_type,
+            from: node.from,
+            to: node.to,
+            value: Some(node.value),
+            gas: node.gas,
+            gas_used: node.gas_used,
+            input: node.input,
+            output: node.output,
+            error: node.error,
+            calls: node.calls.into_iter().map(CallTracerCall::from).collect(),
+        }
+    }
+}
+
+/// The account state of a single address, as captured by the
+/// `prestateTracer`.
+#[napi(object)]
+pub struct PrestateTracerAccountState {
+    pub balance: Option<BigInt>,
+    pub nonce: Option<BigInt>,
+    pub code: Option<Buffer>,
+    pub storage: Option<HashMap<String, String>>,
+}
+
+/// The pre- and post-transaction state of every account touched, as captured
+/// by the `prestateTracer`.
+#[napi(object)]
+pub struct PrestateTracerResult {
+    pub pre: HashMap<String, PrestateTracerAccountState>,
+    pub post: HashMap<String, PrestateTracerAccountState>,
+}
+
+/// A single entry of the `4byteTracer` histogram, keyed by
+/// `selector-calldatalen`.
+#[napi(object)]
+pub struct FourByteTracerEntry {
+    /// `selector-calldatalen`, e.g. `"0x27dc297e-128"`
+    pub key: String,
+    pub count: u32,
+}
+
+/// The result of running one of the built-in tracers, to be serialized to
+/// `serde_json::Value` before being returned through `handle_request`.
+pub enum DebugTraceOutput {
+    /// The default opcode-level struct logger output.
+    StructLogger(DebugTraceResult),
+    /// A recursive call tree.
+    CallTracer(CallTracerCall),
+    /// The pre- and post-state of every touched account.
+    PrestateTracer(PrestateTracerResult),
+    /// A histogram of `selector-calldatalen => count`.
+    FourByteTracer(Vec<FourByteTracerEntry>),
+}
+
+impl DebugTraceOutput {
+    /// Builds the output of the tracer selected by `config` from a
+    /// completed transaction `trace`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `config` selects [`DebugTraceTracer::PrestateTracer`]:
+    /// it needs a snapshot of account state before and after the transaction,
+    /// which isn't reconstructable from a `Trace` alone (it would require
+    /// access to the state DB this binding layer doesn't have), so rather
+    /// than claim success with a permanently-empty `pre`/`post` (which reads
+    /// as "nothing changed"), the tracer is rejected outright.
+    pub fn new(trace: &edr_evm::trace::Trace, config: &DebugTraceConfig) -> napi::Result<Self> {
+        match config.tracer {
+            Some(DebugTraceTracer::CallTracer) => {
+                let root = fold_call_tree(&trace.messages)
+                    .into_iter()
+                    .next()
+                    .expect("a trace always contains at least one top-level call");
+
+                Ok(Self::CallTracer(CallTracerCall::from(root)))
+            }
+            Some(DebugTraceTracer::PrestateTracer) => Err(napi::Error::new(
+                napi::Status::GenericFailure,
+                "prestateTracer is not supported: reconstructing account pre-/post-state \
+                 requires access to the state DB, which this binding layer doesn't have",
+            )),
+            Some(DebugTraceTracer::FourByteTracer) => {
+                Ok(Self::FourByteTracer(four_byte_histogram(trace)))
+            }
+            None => Ok(Self::StructLogger(struct_logger_result(trace, config))),
+        }
+    }
+}
+
+fn four_byte_histogram(trace: &edr_evm::trace::Trace) -> Vec<FourByteTracerEntry> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for message in &trace.messages {
+        if let TraceMessage::Before(message) = message {
+            if message.data.len() >= 4 {
+                let selector: String = message.data[..4]
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect();
+                *counts
+                    .entry(format!("0x{selector}-{}", message.data.len() - 4))
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut entries: Vec<_> = counts
+        .into_iter()
+        .map(|(key, count)| FourByteTracerEntry { key, count })
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries
+}
+
+fn struct_logger_result(trace: &edr_evm::trace::Trace, config: &DebugTraceConfig) -> DebugTraceResult {
+    let enable_memory = config.enable_memory.unwrap_or(false);
+    let disable_stack = config.disable_stack.unwrap_or(false);
+    let disable_storage = config.disable_storage.unwrap_or(false);
+    let limit = config.limit.map(|limit| limit as usize);
+
+    let mut struct_logs = Vec::new();
+    for message in &trace.messages {
+        if let TraceMessage::Step(step) = message {
+            if limit.is_some_and(|limit| struct_logs.len() >= limit) {
+                break;
+            }
+            struct_logs.push(DebugTraceLogItem::new(
+                step,
+                enable_memory,
+                disable_stack,
+                disable_storage,
+            ));
+        }
+    }
+
+    let (pass, gas_used, output) = trace
+        .messages
+        .iter()
+        .rev()
+        .find_map(|message| match message {
+            TraceMessage::After(message) => Some(root_result(message)),
+            _ => None,
+        })
+        .unwrap_or((true, 0, None));
+
+    DebugTraceResult {
+        pass,
+        gas_used: BigInt::from(gas_used),
+        output: output.map(Buffer::from),
+        struct_logs,
+    }
+}
+
+fn root_result(message: &edr_evm::trace::AfterMessage) -> (bool, u64, Option<Vec<u8>>) {
+    match &message.execution_result {
+        edr_evm::ExecutionResult::Success {
+            gas_used, output, ..
+        } => {
+            let output = match output {
+                edr_evm::Output::Call(value) | edr_evm::Output::Create(value, _) => value.to_vec(),
+            };
+            (true, *gas_used, Some(output))
+        }
+        edr_evm::ExecutionResult::Revert { gas_used, output } => {
+            (false, *gas_used, Some(output.to_vec()))
+        }
+        edr_evm::ExecutionResult::Halt { gas_used, .. } => (false, *gas_used, None),
+    }
+}
+
 #[napi(object)]
 pub struct DebugTraceLogItem {
     /// Program Counter
@@ -36,3 +263,58 @@ pub struct DebugTraceLogItem {
     /// Map of all stored values with keys and values encoded as hex strings.
     pub storage: Option<HashMap<String, String>>,
 }
+
+impl DebugTraceLogItem {
+    fn new(
+        step: &edr_evm::trace::Step,
+        enable_memory: bool,
+        disable_stack: bool,
+        disable_storage: bool,
+    ) -> Self {
+        let stack = (!disable_stack).then(|| {
+            step.stack.full().map_or_else(
+                || step.stack.top().into_iter().map(hex_u256).collect(),
+                |stack| stack.iter().map(hex_u256).collect(),
+            )
+        });
+
+        let mem_size = step.memory.as_ref().map_or(0, |memory| memory.len() as u64);
+        let memory = (enable_memory)
+            .then(|| step.memory.as_ref())
+            .flatten()
+            .map(|memory| {
+                memory
+                    .chunks(32)
+                    .map(|word| word.iter().map(|byte| format!("{byte:02x}")).collect())
+                    .collect()
+            });
+
+        let storage = (!disable_storage)
+            .then(|| step.storage.as_ref())
+            .flatten()
+            .map(|storage| {
+                storage
+                    .iter()
+                    .map(|(key, value)| (format!("{key:#x}"), format!("{value:#x}")))
+                    .collect()
+            });
+
+        Self {
+            pc: BigInt::from(step.pc),
+            op: step.opcode,
+            gas: format!("{:#x}", step.gas),
+            gas_cost: format!("{:#x}", step.gas_cost),
+            stack,
+            depth: BigInt::from(step.depth),
+            mem_size: BigInt::from(mem_size),
+            op_name: OpCode::name_by_op(step.opcode).to_string(),
+            error: step.error.clone(),
+            memory,
+            storage,
+        }
+    }
+}
+
+fn hex_u256(value: &edr_evm::U256) -> String {
+    format!("{value:#x}")
+}
@@ -18,6 +18,7 @@ mod provider;
 mod result;
 #[cfg(feature = "scenarios")]
 mod scenarios;
+mod step_callback;
 mod subscribe;
 mod trace;
 mod withdrawal;
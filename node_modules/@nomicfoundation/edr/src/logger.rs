@@ -1,7 +1,7 @@
-use std::{fmt::Display, sync::mpsc::channel};
+use std::{collections::HashMap, fmt::Display, sync::mpsc::channel};
 
 use ansi_term::{Color, Style};
-use edr_eth::{transaction::Transaction, Bytes, B256, U256};
+use edr_eth::{transaction::Transaction, Address, Bytes, B256, U256};
 use edr_evm::{
     blockchain::BlockchainError,
     precompile::{self, Precompiles},
@@ -42,6 +42,85 @@ struct ContractAndFunctionNameCall {
     calldata: Option<Bytes>,
 }
 
+/// A decoded Ethereum event log, as resolved by the JS-side ABI decoder.
+#[napi(object)]
+pub struct DecodedEvent {
+    /// The event's name, e.g. `Transfer`.
+    pub name: String,
+    /// The event's decoded parameters, rendered as `"name: value"` strings.
+    pub params: Vec<String>,
+}
+
+struct DecodeEventCall {
+    address: Bytes,
+    topics: Vec<Bytes>,
+    data: Bytes,
+}
+
+/// An eth-log-filter-style matcher: a set of addresses and/or
+/// position-indexed topic matchers (`OR` within a position, `AND` across
+/// positions), plus an "only failed transactions" toggle, used to cut down
+/// on logger noise for large auto-mined blocks.
+#[napi(object)]
+pub struct LogFilterOptions {
+    /// Only transactions whose `to` address or emitted-log emitter matches
+    /// one of these addresses are shown. `None` matches every address.
+    pub addresses: Option<Vec<Buffer>>,
+    /// Only transactions with an emitted log whose topics match this
+    /// pattern are shown. Each element is a position; `None` at a position
+    /// matches any topic there. `None` overall matches every topic pattern.
+    pub topics: Option<Vec<Option<Vec<Buffer>>>>,
+    /// Only show transactions that reverted or halted.
+    pub only_failed: Option<bool>,
+}
+
+struct LogFilter {
+    addresses: Option<Vec<Address>>,
+    topics: Option<Vec<Option<Vec<B256>>>>,
+    only_failed: bool,
+}
+
+impl TryFrom<LogFilterOptions> for LogFilter {
+    type Error = napi::Error;
+
+    fn try_from(value: LogFilterOptions) -> Result<Self, Self::Error> {
+        let addresses = value
+            .addresses
+            .map(|addresses| {
+                addresses
+                    .into_iter()
+                    .map(TryCast::try_cast)
+                    .collect::<Result<_, napi::Error>>()
+            })
+            .transpose()?;
+
+        let topics = value
+            .topics
+            .map(|topics| {
+                topics
+                    .into_iter()
+                    .map(|position| {
+                        position
+                            .map(|candidates| {
+                                candidates
+                                    .into_iter()
+                                    .map(TryCast::try_cast)
+                                    .collect::<Result<_, napi::Error>>()
+                            })
+                            .transpose()
+                    })
+                    .collect::<Result<_, napi::Error>>()
+            })
+            .transpose()?;
+
+        Ok(Self {
+            addresses,
+            topics,
+            only_failed: value.only_failed.unwrap_or(false),
+        })
+    }
+}
+
 #[napi(object)]
 pub struct LoggerConfig {
     /// Whether to enable the logger.
@@ -50,8 +129,71 @@ pub struct LoggerConfig {
     pub decode_console_log_inputs_callback: JsFunction,
     #[napi(ts_type = "(code: Buffer, calldata?: Buffer) => ContractAndFunctionName")]
     pub get_contract_and_function_name_callback: JsFunction,
+    /// Resolves an emitted event log's ABI, returning `undefined` if no
+    /// matching ABI entry is found. When absent, emitted logs are rendered
+    /// as raw topic/data hex.
+    #[napi(ts_type = "(address: Buffer, topics: Buffer[], data: Buffer) => DecodedEvent | undefined")]
+    pub decode_event_callback: Option<JsFunction>,
     #[napi(ts_type = "(message: string, replace: boolean) => void")]
     pub print_line_callback: JsFunction,
+    /// The output format to emit log lines in. Defaults to `pretty`.
+    pub format: Option<LoggerFormat>,
+    /// An address/topic filter cutting down on noise for large auto-mined
+    /// blocks. See [`LogFilterOptions`].
+    pub filter: Option<LogFilterOptions>,
+    /// Prints a step-by-step opcode disassembly (depth, program counter,
+    /// opcode, gas remaining and gas consumed) for every call/transaction,
+    /// mirroring `debug_traceTransaction`. When unset or `false`, the trace
+    /// is still printed for transactions that revert. Defaults to `false`.
+    pub vm_tracing: Option<bool>,
+}
+
+/// The output format the `Logger` renders its events in.
+#[derive(Clone, Copy)]
+#[napi(string_enum)]
+pub enum LoggerFormat {
+    /// ANSI-decorated, human-readable prose (the default).
+    Pretty,
+    /// One structured JSON object per logged event, for machine/CI
+    /// consumption.
+    Json,
+}
+
+impl Default for LoggerFormat {
+    fn default() -> Self {
+        Self::Pretty
+    }
+}
+
+/// A single structured logging event, emitted as one JSON object per line
+/// when the logger is configured with [`LoggerFormat::Json`].
+#[derive(serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum LogEvent<'a> {
+    Call {
+        from: String,
+        to: Option<String>,
+        value: String,
+        contract_function: Option<String>,
+        console_logs: &'a [String],
+        failure_reason: Option<String>,
+    },
+    SendTransaction {
+        transaction_hash: String,
+        block_number: u64,
+        block_hash: String,
+        from: String,
+        to: Option<String>,
+        value: String,
+        gas_used: String,
+        contract_function: Option<String>,
+        console_logs: &'a [String],
+        failure_reason: Option<String>,
+    },
+    EmptyBlockRange {
+        start_block_number: u64,
+        end_block_number: u64,
+    },
 }
 
 #[derive(Clone)]
@@ -104,6 +246,25 @@ enum LogLine {
 pub enum LoggerError {
     #[error("Failed to print line")]
     PrintLine,
+    /// Decoding a `console.log` call's arguments via
+    /// `decode_console_log_inputs_callback` failed.
+    #[error("Failed to decode console.log arguments")]
+    DecodeConsoleLog,
+    /// A threadsafe-function call into JS (other than `console.log`
+    /// decoding) didn't complete successfully, e.g. the call status wasn't
+    /// `Ok` or the return channel was dropped before sending a result.
+    #[error("Failed to call into JavaScript")]
+    ThreadsafeCall,
+    /// A `Trace` didn't have the shape a logging helper expected, e.g. a
+    /// `Before` message without a matching `After` message.
+    #[error("Encountered a malformed execution trace")]
+    MalformedTrace,
+    /// [`LogCollector::replace_last_log_line`] was called with no log line
+    /// buffered to replace. `self.logs` is flushed to empty at the end of
+    /// each top-level provider call, so this can happen if a caller invokes
+    /// a "replace" logging path as the very first thing in a new call.
+    #[error("No log line to replace")]
+    MissingLogLine,
 }
 
 #[derive(Clone)]
@@ -117,6 +278,40 @@ impl Logger {
             collector: LogCollector::new(env, config)?,
         })
     }
+
+    /// Replaces the address/topic filter used to cut down on logger noise.
+    /// Pass `None` to disable filtering and show every transaction again.
+    pub fn set_log_filter(&mut self, filter: Option<LogFilterOptions>) -> napi::Result<()> {
+        self.collector.filter = filter.map(LogFilter::try_from).transpose()?;
+
+        Ok(())
+    }
+
+    /// Enables or disables the opcode-level "VM trace:" section printed
+    /// alongside calls and transactions. Reverting transactions always
+    /// print a trace regardless of this setting.
+    pub fn set_vm_tracing(&mut self, vm_tracing: bool) {
+        self.collector.vm_tracing = vm_tracing;
+    }
+
+    /// Prints the accumulated per-function gas usage summary. Intended to be
+    /// called once a test/mine session finishes. Degrades gracefully (see
+    /// [`LogCollector::log_render_failure`]) rather than failing the caller
+    /// if the JS-side print channel is gone.
+    pub fn print_gas_report(&mut self) {
+        self.collector.print_gas_report();
+    }
+
+    /// Prints a collapsible `"eth_simulate (N calls)"` header followed by
+    /// one indented `contract#function` line per call in the bundle. See
+    /// [`LogCollector::log_call_bundle`].
+    pub fn log_call_bundle(
+        &mut self,
+        spec_id: edr_eth::SpecId,
+        results: &[edr_provider::CallResult],
+    ) {
+        self.collector.log_call_bundle(spec_id, results);
+    }
 }
 
 impl edr_provider::Logger for Logger {
@@ -155,12 +350,25 @@ impl edr_provider::Logger for Logger {
         Ok(())
     }
 
+    fn log_multicall(
+        &mut self,
+        spec_id: edr_eth::SpecId,
+        transactions: &[ExecutableTransaction],
+        results: &[edr_provider::CallResult],
+    ) -> Result<(), Self::LoggerError> {
+        self.collector.log_multicall(spec_id, transactions, results);
+
+        Ok(())
+    }
+
     fn log_interval_mined(
         &mut self,
         spec_id: edr_eth::SpecId,
         mining_result: &edr_provider::DebugMineBlockResult<Self::BlockchainError>,
     ) -> Result<(), Self::LoggerError> {
-        self.collector.log_interval_mined(spec_id, mining_result)
+        self.collector.log_interval_mined(spec_id, mining_result);
+
+        Ok(())
     }
 
     fn log_mined_block(
@@ -237,17 +445,56 @@ pub struct CollapsedMethod {
     method: String,
 }
 
+/// Running call count/min/max/mean/total gas usage for a single
+/// `contract#function` key, accumulated across a session by
+/// [`LogCollector::record_gas_usage`] and rendered by
+/// [`LogCollector::print_gas_report`].
+#[derive(Clone, Default)]
+struct GasStats {
+    calls: u64,
+    min: u64,
+    max: u64,
+    total: u64,
+}
+
+impl GasStats {
+    fn record(&mut self, gas_used: u64) {
+        self.min = if self.calls == 0 {
+            gas_used
+        } else {
+            self.min.min(gas_used)
+        };
+        self.max = self.max.max(gas_used);
+        self.total += gas_used;
+        self.calls += 1;
+    }
+
+    fn mean(&self) -> u64 {
+        if self.calls == 0 {
+            0
+        } else {
+            self.total / self.calls
+        }
+    }
+}
+
 #[derive(Clone)]
 struct LogCollector {
     decode_console_log_inputs_fn: ThreadsafeFunction<Vec<Bytes>, ErrorStrategy::Fatal>,
+    decode_event_fn: Option<ThreadsafeFunction<DecodeEventCall, ErrorStrategy::Fatal>>,
+    filter: Option<LogFilter>,
+    format: LoggerFormat,
+    gas_stats: HashMap<String, GasStats>,
     get_contract_and_function_name_fn:
         ThreadsafeFunction<ContractAndFunctionNameCall, ErrorStrategy::Fatal>,
+    hidden_transactions: usize,
     indentation: usize,
     is_enabled: bool,
     logs: Vec<LogLine>,
     print_line_fn: ThreadsafeFunction<(String, bool), ErrorStrategy::Fatal>,
     state: LoggingState,
     title_length: usize,
+    vm_tracing: bool,
 }
 
 impl LogCollector {
@@ -303,6 +550,41 @@ impl LogCollector {
         // exiting.
         get_contract_and_function_name_fn.unref(env)?;
 
+        let decode_event_fn = config
+            .decode_event_callback
+            .map(|callback| {
+                let mut decode_event_fn = callback.create_threadsafe_function(
+                    0,
+                    |ctx: ThreadSafeCallContext<DecodeEventCall>| {
+                        let address = ctx
+                            .env
+                            .create_buffer_with_data(ctx.value.address.to_vec())?
+                            .into_unknown();
+
+                        let mut topics = ctx.env.create_array_with_length(ctx.value.topics.len())?;
+                        for (idx, topic) in ctx.value.topics.into_iter().enumerate() {
+                            ctx.env
+                                .create_buffer_with_data(topic.to_vec())
+                                .and_then(|topic| topics.set_element(idx as u32, topic.into_raw()))?;
+                        }
+
+                        let data = ctx
+                            .env
+                            .create_buffer_with_data(ctx.value.data.to_vec())?
+                            .into_unknown();
+
+                        Ok(vec![address, topics.coerce_to_object()?.into_unknown(), data])
+                    },
+                )?;
+
+                // Maintain a weak reference to the function to avoid the event loop from
+                // exiting.
+                decode_event_fn.unref(env)?;
+
+                Ok::<_, napi::Error>(decode_event_fn)
+            })
+            .transpose()?;
+
         let mut print_line_fn = config.print_line_callback.create_threadsafe_function(
             0,
             |ctx: ThreadSafeCallContext<(String, bool)>| {
@@ -320,15 +602,23 @@ impl LogCollector {
         // exiting.
         print_line_fn.unref(env)?;
 
+        let filter = config.filter.map(LogFilter::try_from).transpose()?;
+
         Ok(Self {
             decode_console_log_inputs_fn,
+            decode_event_fn,
+            filter,
+            format: config.format.unwrap_or_default(),
+            gas_stats: HashMap::new(),
             get_contract_and_function_name_fn,
+            hidden_transactions: 0,
             indentation: 0,
             is_enabled: config.enable,
             logs: Vec::new(),
             print_line_fn,
             state: LoggingState::default(),
             title_length: 0,
+            vm_tracing: config.vm_tracing.unwrap_or(false),
         })
     }
 
@@ -346,8 +636,40 @@ impl LogCollector {
 
         self.state = LoggingState::Empty;
 
+        if matches!(self.format, LoggerFormat::Json) {
+            let contract_function = match self.json_contract_and_function_name(spec_id, trace) {
+                Ok(contract_function) => contract_function,
+                Err(error) => {
+                    self.log_render_failure(error);
+                    None
+                }
+            };
+            let failure_reason = TransactionFailure::from_execution_result(execution_result, None, trace)
+                .map(|failure| failure.to_string());
+            let console_logs = match self.decode_console_log_messages(console_log_inputs) {
+                Ok(console_logs) => console_logs,
+                Err(error) => {
+                    self.log_render_failure(error);
+                    Vec::new()
+                }
+            };
+
+            self.emit_event(LogEvent::Call {
+                from: format!("0x{:x}", transaction.caller()),
+                to: transaction.to().map(|to| format!("0x{to:x}")),
+                value: transaction.value().to_string(),
+                contract_function,
+                console_logs: &console_logs,
+                failure_reason,
+            });
+
+            return;
+        }
+
         self.indented(|logger| {
-            logger.log_contract_and_function_name::<true>(spec_id, trace);
+            if let Err(error) = logger.log_contract_and_function_name::<true>(spec_id, trace) {
+                logger.log_render_failure(error);
+            }
 
             logger.log_with_title("From", format!("0x{:x}", transaction.caller()));
             if let Some(to) = transaction.to() {
@@ -357,11 +679,16 @@ impl LogCollector {
                 logger.log_with_title("Value", wei_to_human_readable(transaction.value()));
             }
 
-            logger.log_console_log_messages(console_log_inputs);
+            if let Err(error) = logger.log_console_log_messages(console_log_inputs) {
+                logger.log_render_failure(error);
+            }
+            logger.log_emitted_logs(execution_result_logs(execution_result));
 
-            if let Some(transaction_failure) =
-                TransactionFailure::from_execution_result(execution_result, None, trace)
-            {
+            let transaction_failure =
+                TransactionFailure::from_execution_result(execution_result, None, trace);
+            logger.log_vm_trace(trace, transaction_failure.is_some());
+
+            if let Some(transaction_failure) = transaction_failure {
                 logger.log_transaction_failure(&transaction_failure);
             }
         });
@@ -381,10 +708,12 @@ impl LogCollector {
         self.state = LoggingState::Empty;
 
         self.indented(|logger| {
-            logger.log_contract_and_function_name::<true>(
+            if let Err(error) = logger.log_contract_and_function_name::<true>(
                 spec_id,
                 &transaction_failure.failure.solidity_trace,
-            );
+            ) {
+                logger.log_render_failure(error);
+            }
 
             logger.log_with_title("From", format!("0x{:x}", transaction.caller()));
             if let Some(to) = transaction.to() {
@@ -392,12 +721,114 @@ impl LogCollector {
             }
             logger.log_with_title("Value", wei_to_human_readable(transaction.value()));
 
-            logger.log_console_log_messages(console_log_inputs);
+            if let Err(error) = logger.log_console_log_messages(console_log_inputs) {
+                logger.log_render_failure(error);
+            }
 
             logger.log_transaction_failure(&transaction_failure.failure);
         });
     }
 
+    /// Logs a batch of calls executed against shared speculative state (the
+    /// multicall pattern), printing a numbered "Call N of M" header per
+    /// entry and a final summary of how many calls reverted.
+    pub fn log_multicall(
+        &mut self,
+        spec_id: edr_eth::SpecId,
+        transactions: &[ExecutableTransaction],
+        results: &[edr_provider::CallResult],
+    ) {
+        self.state = LoggingState::Empty;
+
+        let num_calls = results.len();
+        let mut num_reverted = 0;
+
+        self.indented(|logger| {
+            for (idx, transaction, result) in
+                izip!(0..num_calls, transactions, results)
+            {
+                let edr_provider::CallResult {
+                    console_log_inputs,
+                    execution_result,
+                    trace,
+                } = result;
+
+                logger.log_with_title(format!("Call {}", idx + 1), format!("of {num_calls}"));
+
+                logger.indented(|logger| {
+                    if let Err(error) = logger.log_contract_and_function_name::<true>(spec_id, trace) {
+                        logger.log_render_failure(error);
+                    }
+
+                    logger.log_with_title("From", format!("0x{:x}", transaction.caller()));
+                    if let Some(to) = transaction.to() {
+                        logger.log_with_title("To", format!("0x{to:x}"));
+                    }
+                    if transaction.value() > U256::ZERO {
+                        logger.log_with_title("Value", wei_to_human_readable(transaction.value()));
+                    }
+
+                    if let Err(error) = logger.log_console_log_messages(console_log_inputs) {
+                        logger.log_render_failure(error);
+                    }
+
+                    if let Some(transaction_failure) =
+                        TransactionFailure::from_execution_result(execution_result, None, trace)
+                    {
+                        num_reverted += 1;
+                        logger.log_transaction_failure(&transaction_failure);
+                    }
+                });
+
+                logger.log_empty_line_between_transactions(idx, num_calls);
+            }
+
+            logger.log_empty_line();
+            logger.log(format!("{num_reverted} of {num_calls} calls reverted"));
+        });
+    }
+
+    /// Prints a single collapsible `"eth_simulate (N calls)"` header for a
+    /// batch of calls executed against shared pending state, then logs each
+    /// sub-call indented underneath, one `contract#function` line per call.
+    /// Repeated bundles of the same size collapse into a single counted
+    /// header the same way [`Self::print_method`] collapses repeated single
+    /// calls, since the header text itself encodes the bundle size.
+    pub fn log_call_bundle(
+        &mut self,
+        spec_id: edr_eth::SpecId,
+        results: &[edr_provider::CallResult],
+    ) {
+        let num_calls = results.len();
+
+        if let Err(error) = self.print_method(&format!("eth_simulate ({num_calls} calls)")) {
+            self.log_render_failure(error);
+            return;
+        }
+
+        self.indented(|logger| {
+            for (idx, result) in results.iter().enumerate() {
+                if let Err(error) =
+                    logger.log_contract_and_function_name::<false>(spec_id, &result.trace)
+                {
+                    logger.log_render_failure(error);
+                }
+                logger.log_empty_line_between_transactions(idx, num_calls);
+            }
+        });
+
+        match self.print_logs() {
+            Ok(printed) => {
+                if printed {
+                    if let Err(error) = self.print_empty_line() {
+                        self.log_render_failure(error);
+                    }
+                }
+            }
+            Err(error) => self.log_render_failure(error),
+        }
+    }
+
     fn log_transaction_failure(&mut self, failure: &edr_provider::TransactionFailure) {
         let is_revert_error = matches!(
             failure.reason,
@@ -425,7 +856,11 @@ impl LogCollector {
             let empty_blocks_range_start = state.into_hardhat_mining();
 
             if mining_result.block.transactions().is_empty() {
-                self.log_hardhat_mined_empty_block(&mining_result.block, empty_blocks_range_start);
+                if let Err(error) = self
+                    .log_hardhat_mined_empty_block(&mining_result.block, empty_blocks_range_start)
+                {
+                    self.log_render_failure(error);
+                }
 
                 let block_number = mining_result.block.header().number;
                 self.state = LoggingState::HardhatMinining {
@@ -447,7 +882,7 @@ impl LogCollector {
         &mut self,
         spec_id: edr_eth::SpecId,
         mining_result: &edr_provider::DebugMineBlockResult<BlockchainError>,
-    ) -> Result<(), LoggerError> {
+    ) {
         let block_header = mining_result.block.header();
         let block_number = block_header.number;
 
@@ -455,10 +890,10 @@ impl LogCollector {
             let state = std::mem::take(&mut self.state);
             let empty_blocks_range_start = state.into_interval_mining();
 
-            if let Some(empty_blocks_range_start) = empty_blocks_range_start {
+            let result = if let Some(empty_blocks_range_start) = empty_blocks_range_start {
                 self.print::<true>(format!(
                     "Mined empty block range #{empty_blocks_range_start} to #{block_number}"
-                ))?;
+                ))
             } else {
                 let base_fee = if let Some(base_fee) = block_header.base_fee_per_gas.as_ref() {
                     format!(" with base fee {base_fee}")
@@ -466,7 +901,10 @@ impl LogCollector {
                     String::new()
                 };
 
-                self.print::<false>(format!("Mined empty block #{block_number}{base_fee}"))?;
+                self.print::<false>(format!("Mined empty block #{block_number}{base_fee}"))
+            };
+            if let Err(error) = result {
+                self.log_render_failure(error);
             }
 
             self.state = LoggingState::IntervalMining {
@@ -477,15 +915,22 @@ impl LogCollector {
         } else {
             self.log_interval_mined_block(spec_id, mining_result);
 
-            self.print::<false>(format!("Mined block #{block_number}"))?;
+            if let Err(error) = self.print::<false>(format!("Mined block #{block_number}")) {
+                self.log_render_failure(error);
+                return;
+            }
 
-            let printed = self.print_logs()?;
-            if printed {
-                self.print_empty_line()?;
+            match self.print_logs() {
+                Ok(printed) => {
+                    if printed {
+                        if let Err(error) = self.print_empty_line() {
+                            self.log_render_failure(error);
+                        }
+                    }
+                }
+                Err(error) => self.log_render_failure(error),
             }
         }
-
-        Ok(())
     }
 
     pub fn log_send_transaction(
@@ -497,7 +942,7 @@ impl LogCollector {
         if !mining_results.is_empty() {
             self.state = LoggingState::Empty;
 
-            let (sent_block_result, sent_transaction_result, sent_trace) = mining_results
+            let Some((sent_block_result, sent_transaction_result, sent_trace)) = mining_results
                 .iter()
                 .find_map(|result| {
                     izip!(
@@ -510,7 +955,10 @@ impl LogCollector {
                     })
                     .map(|(_, transaction_result, trace)| (result, transaction_result, trace))
                 })
-                .expect("Transaction result not found");
+            else {
+                self.log_render_failure(LoggerError::MalformedTrace);
+                return;
+            };
 
             if mining_results.len() > 1 {
                 self.log_multiple_blocks_warning();
@@ -543,7 +991,11 @@ impl LogCollector {
                         sent_trace,
                     );
                 } else if let Some(transaction) = transactions.first() {
-                    self.log_single_transaction_mining_result(spec_id, result, transaction);
+                    if let Err(error) =
+                        self.log_single_transaction_mining_result(spec_id, result, transaction)
+                    {
+                        self.log_render_failure(error);
+                    }
                 }
             }
         }
@@ -553,7 +1005,7 @@ impl LogCollector {
         &self,
         code: Bytes,
         calldata: Option<Bytes>,
-    ) -> (String, Option<String>) {
+    ) -> Result<(String, Option<String>), LoggerError> {
         let (sender, receiver) = channel();
 
         let status = self
@@ -571,12 +1023,29 @@ impl LogCollector {
                     })
                 },
             );
-        assert_eq!(status, Status::Ok);
 
-        receiver
-            .recv()
-            .unwrap()
-            .expect("Failed call to get_contract_and_function_name")
+        Self::recv_threadsafe(status, receiver)?.map_err(|_error| LoggerError::ThreadsafeCall)
+    }
+
+    /// Waits on a threadsafe-function call's return channel, converting a
+    /// non-`Ok` call status or a closed channel into
+    /// [`LoggerError::ThreadsafeCall`] instead of panicking.
+    fn recv_threadsafe<T>(
+        status: Status,
+        receiver: std::sync::mpsc::Receiver<T>,
+    ) -> Result<T, LoggerError> {
+        if status != Status::Ok {
+            return Err(LoggerError::ThreadsafeCall);
+        }
+
+        receiver.recv().map_err(|_error| LoggerError::ThreadsafeCall)
+    }
+
+    /// Pushes a degraded `"Failed to render log: {error}"` line instead of
+    /// panicking when a rendering helper (console.log decoding, contract/
+    /// function name resolution, event decoding, trace inspection) fails.
+    fn log_render_failure(&mut self, error: LoggerError) {
+        self.log(format!("Failed to render log: {error}"));
     }
 
     fn format(&self, message: impl ToString) -> String {
@@ -679,12 +1148,72 @@ impl LogCollector {
 
                     logger.log_empty_line_between_transactions(idx, num_transactions);
                 }
+
+                logger.flush_hidden_transactions();
             });
         });
 
         self.log_empty_line();
     }
 
+    /// Returns whether a transaction passes the configured [`LogFilter`].
+    /// Always `true` when no filter is configured.
+    fn matches_filter(&self, transaction: &ExecutableTransaction, result: &ExecutionResult) -> bool {
+        let Some(filter) = &self.filter else {
+            return true;
+        };
+
+        if filter.only_failed
+            && !matches!(
+                result,
+                ExecutionResult::Revert { .. } | ExecutionResult::Halt { .. }
+            )
+        {
+            return false;
+        }
+
+        let logs = execution_result_logs(result);
+
+        if let Some(addresses) = &filter.addresses {
+            let matches = transaction.to().is_some_and(|to| addresses.contains(&to))
+                || logs.iter().any(|log| addresses.contains(&log.address));
+
+            if !matches {
+                return false;
+            }
+        }
+
+        if let Some(topics) = &filter.topics {
+            let matches = logs.iter().any(|log| {
+                topics.iter().enumerate().all(|(position, candidates)| {
+                    candidates.as_ref().map_or(true, |candidates| {
+                        log.topics()
+                            .get(position)
+                            .is_some_and(|topic| candidates.contains(topic))
+                    })
+                })
+            });
+
+            if !matches {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Prints a "... N transactions hidden by filter ..." line for any transactions
+    /// skipped by [`Self::matches_filter`] since the last flush.
+    fn flush_hidden_transactions(&mut self) {
+        if self.hidden_transactions > 0 {
+            self.log(format!(
+                "... {} transactions hidden by filter ...",
+                self.hidden_transactions
+            ));
+            self.hidden_transactions = 0;
+        }
+    }
+
     fn log_block_hash(&mut self, block: &dyn SyncBlock<Error = BlockchainError>) {
         let block_hash = block.hash();
 
@@ -714,6 +1243,13 @@ impl LogCollector {
         console_log_inputs: &[Bytes],
         should_highlight_hash: bool,
     ) {
+        if !self.matches_filter(transaction, result) {
+            self.hidden_transactions += 1;
+            return;
+        }
+
+        self.flush_hidden_transactions();
+
         let transaction_hash = transaction.transaction_hash();
         if should_highlight_hash {
             self.log_with_title(
@@ -725,7 +1261,9 @@ impl LogCollector {
         }
 
         self.indented(|logger| {
-            logger.log_contract_and_function_name::<false>(spec_id, trace);
+            if let Err(error) = logger.log_contract_and_function_name::<false>(spec_id, trace) {
+                logger.log_render_failure(error);
+            }
             logger.log_with_title("From", format!("0x{:x}", transaction.caller()));
             if let Some(to) = transaction.to() {
                 logger.log_with_title("To", format!("0x{to:x}"));
@@ -739,14 +1277,24 @@ impl LogCollector {
                     gas_limit = transaction.gas_limit()
                 ),
             );
+            match logger.json_contract_and_function_name(spec_id, trace) {
+                Ok(contract_function) => {
+                    logger.record_gas_usage(contract_function, result.gas_used());
+                }
+                Err(error) => logger.log_render_failure(error),
+            }
 
-            logger.log_console_log_messages(console_log_inputs);
+            if let Err(error) = logger.log_console_log_messages(console_log_inputs) {
+                logger.log_render_failure(error);
+            }
+            logger.log_emitted_logs(execution_result_logs(result));
 
             let transaction_failure = edr_provider::TransactionFailure::from_execution_result(
                 result,
                 Some(transaction_hash),
                 trace,
             );
+            logger.log_vm_trace(trace, transaction_failure.is_some());
 
             if let Some(transaction_failure) = transaction_failure {
                 logger.log_transaction_failure(&transaction_failure);
@@ -754,28 +1302,166 @@ impl LogCollector {
         });
     }
 
-    fn log_console_log_messages(&mut self, console_log_inputs: &[Bytes]) {
+    /// Prints an "Emitted events:" section, one indented block per log with
+    /// "Emitter", "Topics" and "Data" titled lines. Falls back to raw
+    /// topic/data hex when `decode_event_fn` is absent or can't resolve an
+    /// ABI match. Suppressed entirely when the transaction emitted no logs.
+    fn log_emitted_logs(&mut self, logs: &[edr_evm::Log]) {
+        if logs.is_empty() {
+            return;
+        }
+
+        self.log_empty_line();
+        self.log("Emitted events:");
+
+        self.indented(|logger| {
+            for log in logs {
+                let decoded = match logger.decode_event(
+                    log.address,
+                    log.topics().to_vec(),
+                    log.data.data.clone(),
+                ) {
+                    Ok(decoded) => decoded,
+                    Err(error) => {
+                        logger.log_render_failure(error);
+                        None
+                    }
+                };
+
+                logger.indented(|logger| {
+                    logger.log_with_title("Emitter", format!("0x{:x}", log.address));
+
+                    if let Some(DecodedEvent { name, params }) = decoded {
+                        logger.log_with_title(
+                            "Event",
+                            if params.is_empty() {
+                                name
+                            } else {
+                                format!("{name}({})", params.join(", "))
+                            },
+                        );
+                    } else {
+                        let topics = log
+                            .topics()
+                            .iter()
+                            .map(|topic| format!("0x{topic:x}"))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        logger.log_with_title("Topics", topics);
+                        logger.log_with_title("Data", log.data.data.clone());
+                    }
+                });
+            }
+        });
+    }
+
+    /// Prints a "VM trace:" section walking every message in `trace`, one
+    /// indented line per step with the call depth, program counter, opcode
+    /// mnemonic, gas remaining and the gas consumed since the previous step
+    /// at the same depth. `Before`/`After` boundaries log the `CALL`/
+    /// `CREATE` target, value and returned status, indented by depth so
+    /// nested frames are visually nested. Fires when `vm_tracing` is
+    /// enabled, and always when `reverted` is `true`, so failures get a
+    /// trace even with the flag off.
+    fn log_vm_trace(&mut self, trace: &edr_evm::trace::Trace, reverted: bool) {
+        if !self.vm_tracing && !reverted {
+            return;
+        }
+
+        self.log_empty_line();
+        self.log("VM trace:");
+
+        let base_indentation = self.indentation;
+        let mut gas_remaining_by_depth: Vec<Option<u64>> = Vec::new();
+
+        for message in &trace.messages {
+            match message {
+                TraceMessage::Before(before) => {
+                    self.indentation = base_indentation + 2 + before.depth as usize * 2;
+
+                    let kind = if before.to.is_some() { "CALL" } else { "CREATE" };
+                    let target = before
+                        .to
+                        .map_or_else(|| "<new>".to_string(), |to| format!("0x{to:x}"));
+                    self.log(format!("{kind} {target} value={}", before.value));
+                }
+                TraceMessage::Step(step) => {
+                    let depth = step.depth as usize;
+                    if gas_remaining_by_depth.len() <= depth {
+                        gas_remaining_by_depth.resize(depth + 1, None);
+                    }
+
+                    let gas_remaining = step.gas_remaining;
+                    let gas_cost = gas_remaining_by_depth[depth]
+                        .map_or(0, |previous: u64| previous.saturating_sub(gas_remaining));
+                    gas_remaining_by_depth[depth] = Some(gas_remaining);
+
+                    self.indentation = base_indentation + 2 + depth * 2;
+                    self.log(format!(
+                        "[{depth}] pc={pc} {opcode} gas={gas_remaining} cost={gas_cost}",
+                        pc = step.pc,
+                        opcode = edr_evm::interpreter::OpCode::name_by_op(step.opcode),
+                    ));
+                }
+                TraceMessage::After(AfterMessage {
+                    execution_result, ..
+                }) => {
+                    let status = match execution_result {
+                        ExecutionResult::Success { .. } => "success",
+                        ExecutionResult::Revert { .. } => "revert",
+                        ExecutionResult::Halt { .. } => "halt",
+                    };
+                    self.log(format!("=> {status}"));
+                }
+            }
+        }
+
+        self.indentation = base_indentation;
+    }
+
+    /// Resolves an emitted log's ABI via `decode_event_fn`, if configured.
+    fn decode_event(
+        &self,
+        address: Address,
+        topics: Vec<B256>,
+        data: Bytes,
+    ) -> Result<Option<DecodedEvent>, LoggerError> {
+        let Some(decode_event_fn) = self.decode_event_fn.as_ref() else {
+            return Ok(None);
+        };
+
         let (sender, receiver) = channel();
 
-        let status = self.decode_console_log_inputs_fn.call_with_return_value(
-            console_log_inputs.to_vec(),
+        let status = decode_event_fn.call_with_return_value(
+            DecodeEventCall {
+                address: Bytes::copy_from_slice(address.as_slice()),
+                topics: topics
+                    .into_iter()
+                    .map(|topic| Bytes::copy_from_slice(topic.as_slice()))
+                    .collect(),
+                data,
+            },
             ThreadsafeFunctionCallMode::Blocking,
-            move |decoded_inputs: Vec<String>| {
-                sender.send(decoded_inputs).map_err(|_error| {
+            move |decoded: Option<DecodedEvent>| {
+                sender.send(decoded).map_err(|_error| {
                     napi::Error::new(
                         Status::GenericFailure,
-                        "Failed to send result from decode_console_log_inputs",
+                        "Failed to send result from decode_event_callback",
                     )
                 })
             },
         );
-        assert_eq!(status, Status::Ok);
 
-        let console_log_inputs = receiver.recv().unwrap();
+        Self::recv_threadsafe(status, receiver)
+    }
+
+    fn log_console_log_messages(&mut self, console_log_inputs: &[Bytes]) -> Result<(), LoggerError> {
         // This is a special case, as we always want to print the console.log messages.
         // The difference is how. If we have a logger, we should use that, so that logs
         // are printed in order. If we don't, we just print the messages here.
         if self.is_enabled {
+            let console_log_inputs = self.decode_console_log_messages(console_log_inputs)?;
+
             if !console_log_inputs.is_empty() {
                 self.log_empty_line();
                 self.log("console.log:");
@@ -787,21 +1473,111 @@ impl LogCollector {
                 });
             }
         } else {
+            let console_log_inputs = self.decode_console_log_messages(console_log_inputs)?;
+
             for input in console_log_inputs {
                 let status = self
                     .print_line_fn
                     .call((input, false), ThreadsafeFunctionCallMode::Blocking);
 
-                assert_eq!(status, napi::Status::Ok);
+                if status != napi::Status::Ok {
+                    return Err(LoggerError::ThreadsafeCall);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes `console.log` inputs without printing them, for use by the
+    /// structured (JSON) logging path and by [`Self::log_console_log_messages`].
+    fn decode_console_log_messages(
+        &self,
+        console_log_inputs: &[Bytes],
+    ) -> Result<Vec<String>, LoggerError> {
+        let (sender, receiver) = channel();
+
+        let status = self.decode_console_log_inputs_fn.call_with_return_value(
+            console_log_inputs.to_vec(),
+            ThreadsafeFunctionCallMode::Blocking,
+            move |decoded_inputs: Vec<String>| {
+                sender.send(decoded_inputs).map_err(|_error| {
+                    napi::Error::new(
+                        Status::GenericFailure,
+                        "Failed to send result from decode_console_log_inputs",
+                    )
+                })
+            },
+        );
+
+        Self::recv_threadsafe(status, receiver).map_err(|_error| LoggerError::DecodeConsoleLog)
+    }
+
+    /// Resolves the `contract#function` (or `<PrecompileContract N>`) label
+    /// for a trace's first message, without logging it, for use by the
+    /// structured (JSON) logging path.
+    fn json_contract_and_function_name(
+        &self,
+        spec_id: edr_eth::SpecId,
+        trace: &edr_evm::trace::Trace,
+    ) -> Result<Option<String>, LoggerError> {
+        let Some(TraceMessage::Before(before_message)) = trace.messages.first() else {
+            return Ok(None);
+        };
+
+        if let Some(to) = before_message.to {
+            let is_precompile = {
+                let precompiles =
+                    Precompiles::new(precompile::PrecompileSpecId::from_spec_id(spec_id));
+                precompiles.contains(&to)
+            };
+
+            if is_precompile {
+                let precompile = u16::from_be_bytes([to[18], to[19]]);
+                return Ok(Some(format!("<PrecompileContract {precompile}>")));
             }
+
+            let Some(code) = before_message.code.as_ref() else {
+                return Ok(None);
+            };
+            if edr_evm::Bytecode::is_empty(code) {
+                return Ok(None);
+            }
+
+            let (contract_name, function_name) = self.contract_and_function_name(
+                edr_evm::Bytecode::original_bytes(code),
+                Some(before_message.data.clone()),
+            )?;
+
+            Ok(Some(match function_name {
+                Some(function_name) if !function_name.is_empty() => {
+                    format!("{contract_name}#{function_name}")
+                }
+                _ => contract_name,
+            }))
+        } else {
+            let (contract_name, _) =
+                self.contract_and_function_name(before_message.data.clone(), None)?;
+
+            Ok(Some(format!("deploy:{contract_name}")))
         }
     }
 
+    /// Serializes a [`LogEvent`] to a single line of newline-delimited JSON
+    /// and queues it for printing, mirroring how pretty-printed lines are
+    /// queued via [`Self::log`].
+    fn emit_event(&mut self, event: LogEvent) {
+        let json = serde_json::to_string(&event)
+            .unwrap_or_else(|error| format!(r#"{{"kind":"serialization_error","message":"{error}"}}"#));
+
+        self.logs.push(LogLine::Single(json));
+    }
+
     fn log_contract_and_function_name<const PRINT_INVALID_CONTRACT_WARNING: bool>(
         &mut self,
         spec_id: edr_eth::SpecId,
         trace: &edr_evm::trace::Trace,
-    ) {
+    ) -> Result<(), LoggerError> {
         if let Some(TraceMessage::Before(before_message)) = trace.messages.first() {
             if let Some(to) = before_message.to {
                 // Call
@@ -828,16 +1604,21 @@ impl LogCollector {
                             self.log("WARNING: Calling an account which is not a contract");
                         }
                     } else {
+                        let Some(code) = before_message
+                            .code
+                            .as_ref()
+                            .map(edr_evm::Bytecode::original_bytes)
+                        else {
+                            return Err(LoggerError::MalformedTrace);
+                        };
+
                         let (contract_name, function_name) = self.contract_and_function_name(
-                            before_message
-                                .code
-                                .as_ref()
-                                .map(edr_evm::Bytecode::original_bytes)
-                                .expect("Call must be defined"),
+                            code,
                             Some(before_message.data.clone()),
-                        );
+                        )?;
 
-                        let function_name = function_name.expect("Function name must be defined");
+                        let function_name =
+                            function_name.ok_or(LoggerError::MalformedTrace)?;
                         self.log_with_title(
                             "Contract call",
                             if function_name.is_empty() {
@@ -849,23 +1630,20 @@ impl LogCollector {
                     }
                 }
             } else {
-                let result = if let Some(TraceMessage::After(AfterMessage {
-                    execution_result,
-                    ..
+                let Some(TraceMessage::After(AfterMessage {
+                    execution_result, ..
                 })) = trace.messages.last()
-                {
-                    execution_result
-                } else {
-                    unreachable!("Before messages must have an after message")
+                else {
+                    return Err(LoggerError::MalformedTrace);
                 };
 
                 // Create
                 let (contract_name, _) =
-                    self.contract_and_function_name(before_message.data.clone(), None);
+                    self.contract_and_function_name(before_message.data.clone(), None)?;
 
                 self.log_with_title("Contract deployment", contract_name);
 
-                if let ExecutionResult::Success { output, .. } = result {
+                if let ExecutionResult::Success { output, .. } = execution_result {
                     if let edr_evm::Output::Create(_, address) = output {
                         if let Some(deployed_address) = address {
                             self.log_with_title(
@@ -874,11 +1652,13 @@ impl LogCollector {
                             );
                         }
                     } else {
-                        unreachable!("Create calls must return a Create output")
+                        return Err(LoggerError::MalformedTrace);
                     }
                 }
             }
         }
+
+        Ok(())
     }
 
     fn log_empty_block(&mut self, block: &dyn SyncBlock<Error = BlockchainError>) {
@@ -908,17 +1688,34 @@ impl LogCollector {
         &mut self,
         block: &dyn SyncBlock<Error = BlockchainError>,
         empty_blocks_range_start: Option<u64>,
-    ) {
-        self.indented(|logger| {
+    ) -> Result<(), LoggerError> {
+        let block_number = block.header().number;
+
+        if matches!(self.format, LoggerFormat::Json) {
+            if let Some(empty_blocks_range_start) = empty_blocks_range_start {
+                // Replace the previous range's event, rather than in-place string
+                // rewriting, since start/end are now explicit fields.
+                self.logs.pop();
+            }
+
+            self.emit_event(LogEvent::EmptyBlockRange {
+                start_block_number: empty_blocks_range_start.unwrap_or(block_number),
+                end_block_number: block_number,
+            });
+
+            return Ok(());
+        }
+
+        self.try_indented(|logger| {
             if let Some(empty_blocks_range_start) = empty_blocks_range_start {
                 logger.replace_last_log_line(format!(
-                    "Mined empty block range #{empty_blocks_range_start} to #{block_number}",
-                    block_number = block.header().number
-                ));
+                    "Mined empty block range #{empty_blocks_range_start} to #{block_number}"
+                ))
             } else {
                 logger.log_empty_block(block);
+                Ok(())
             }
-        });
+        })
     }
 
     /// Logs the result of interval mining a block.
@@ -965,6 +1762,8 @@ impl LogCollector {
 
                     logger.log_empty_line_between_transactions(idx, num_transactions);
                 }
+
+                logger.flush_hidden_transactions();
             });
         });
     }
@@ -1016,6 +1815,8 @@ impl LogCollector {
 
                             logger.log_empty_line_between_transactions(idx, num_transactions);
                         }
+
+                        logger.flush_hidden_transactions();
                     });
                 });
             }
@@ -1039,6 +1840,57 @@ impl LogCollector {
         self.log_empty_line();
     }
 
+    /// Accumulates `gas_used` under `contract_function`'s running
+    /// [`GasStats`], for later rendering by [`Self::print_gas_report`].
+    fn record_gas_usage(&mut self, contract_function: Option<String>, gas_used: u64) {
+        if let Some(contract_function) = contract_function {
+            self.gas_stats.entry(contract_function).or_default().record(gas_used);
+        }
+    }
+
+    /// Renders a gas usage summary table, one row per resolved
+    /// `contract#function` key, sorted by total gas descending. Respects the
+    /// `title_length` column-alignment logic used by [`Self::log_with_title`].
+    /// No-op if no gas usage has been recorded this session.
+    pub fn print_gas_report(&mut self) {
+        if self.gas_stats.is_empty() {
+            return;
+        }
+
+        let mut entries: Vec<(String, GasStats)> = self
+            .gas_stats
+            .iter()
+            .map(|(key, stats)| (key.clone(), stats.clone()))
+            .collect();
+        entries.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+
+        self.log("Gas usage report:");
+        self.indented(|logger| {
+            for (contract_function, stats) in entries {
+                logger.log_with_title(
+                    contract_function,
+                    format!(
+                        "{} calls, min {}, max {}, mean {}, total {}",
+                        stats.calls,
+                        stats.min,
+                        stats.max,
+                        stats.mean(),
+                        stats.total
+                    ),
+                );
+            }
+        });
+
+        match self.print_logs() {
+            Ok(_) => {
+                if let Err(error) = self.print_empty_line() {
+                    self.log_render_failure(error);
+                }
+            }
+            Err(error) => self.log_render_failure(error),
+        }
+    }
+
     fn log_with_title(&mut self, title: impl Into<String>, message: impl Display) {
         // repeat whitespace self.indentation times and concatenate with title
         let title = format!("{:indent$}{}", "", title.into(), indent = self.indentation);
@@ -1077,18 +1929,20 @@ impl LogCollector {
         spec_id: edr_eth::SpecId,
         result: &edr_provider::DebugMineBlockResult<BlockchainError>,
         transaction: &ExecutableTransaction,
-    ) {
+    ) -> Result<(), LoggerError> {
         let trace = result
             .transaction_traces
             .first()
-            .expect("A transaction exists, so the trace must exist as well.");
+            .ok_or(LoggerError::MalformedTrace)?;
 
         let transaction_result = result
             .transaction_results
             .first()
-            .expect("A transaction exists, so the result must exist as well.");
+            .ok_or(LoggerError::MalformedTrace)?;
 
         self.log_transaction(spec_id, result, transaction, transaction_result, trace);
+
+        Ok(())
     }
 
     fn log_transaction(
@@ -1099,8 +1953,48 @@ impl LogCollector {
         transaction_result: &edr_evm::ExecutionResult,
         trace: &edr_evm::trace::Trace,
     ) {
+        if matches!(self.format, LoggerFormat::Json) {
+            let contract_function = match self.json_contract_and_function_name(spec_id, trace) {
+                Ok(contract_function) => contract_function,
+                Err(error) => {
+                    self.log_render_failure(error);
+                    None
+                }
+            };
+            let failure_reason = edr_provider::TransactionFailure::from_execution_result(
+                transaction_result,
+                Some(transaction.transaction_hash()),
+                trace,
+            )
+            .map(|failure| failure.to_string());
+            let console_logs = match self.decode_console_log_messages(&block_result.console_log_inputs) {
+                Ok(console_logs) => console_logs,
+                Err(error) => {
+                    self.log_render_failure(error);
+                    Vec::new()
+                }
+            };
+
+            self.emit_event(LogEvent::SendTransaction {
+                transaction_hash: transaction.transaction_hash().to_string(),
+                block_number: block_result.block.header().number,
+                block_hash: block_result.block.hash().to_string(),
+                from: format!("0x{:x}", transaction.caller()),
+                to: transaction.to().map(|to| format!("0x{to:x}")),
+                value: transaction.value().to_string(),
+                gas_used: transaction_result.gas_used().to_string(),
+                contract_function,
+                console_logs: &console_logs,
+                failure_reason,
+            });
+
+            return;
+        }
+
         self.indented(|logger| {
-            logger.log_contract_and_function_name::<false>(spec_id, trace);
+            if let Err(error) = logger.log_contract_and_function_name::<false>(spec_id, trace) {
+                logger.log_render_failure(error);
+            }
 
             let transaction_hash = transaction.transaction_hash();
             logger.log_with_title("Transaction", transaction_hash);
@@ -1118,17 +2012,27 @@ impl LogCollector {
                     gas_limit = transaction.gas_limit()
                 ),
             );
+            match logger.json_contract_and_function_name(spec_id, trace) {
+                Ok(contract_function) => {
+                    logger.record_gas_usage(contract_function, transaction_result.gas_used());
+                }
+                Err(error) => logger.log_render_failure(error),
+            }
 
             let block_number = block_result.block.header().number;
             logger.log_with_title(format!("Block #{block_number}"), block_result.block.hash());
 
-            logger.log_console_log_messages(&block_result.console_log_inputs);
+            if let Err(error) = logger.log_console_log_messages(&block_result.console_log_inputs) {
+                logger.log_render_failure(error);
+            }
+            logger.log_emitted_logs(execution_result_logs(transaction_result));
 
             let transaction_failure = edr_provider::TransactionFailure::from_execution_result(
                 transaction_result,
                 Some(transaction_hash),
                 trace,
             );
+            logger.log_vm_trace(trace, transaction_failure.is_some());
 
             if let Some(transaction_failure) = transaction_failure {
                 logger.log_transaction_failure(&transaction_failure);
@@ -1206,10 +2110,25 @@ impl LogCollector {
         None
     }
 
-    fn replace_last_log_line(&mut self, message: impl ToString) {
+    fn replace_last_log_line(&mut self, message: impl ToString) -> Result<(), LoggerError> {
         let formatted = self.format(message);
 
-        *self.logs.last_mut().expect("There must be a log line") = LogLine::Single(formatted);
+        let last_line = self
+            .logs
+            .last_mut()
+            .ok_or(LoggerError::MissingLogLine)?;
+        *last_line = LogLine::Single(formatted);
+
+        Ok(())
+    }
+}
+
+/// Returns the logs emitted by a successful execution, or an empty slice
+/// otherwise (reverts and halts never emit logs).
+fn execution_result_logs(result: &ExecutionResult) -> &[edr_evm::Log] {
+    match result {
+        ExecutionResult::Success { logs, .. } => logs,
+        ExecutionResult::Revert { .. } | ExecutionResult::Halt { .. } => &[],
     }
 }
 
@@ -4,14 +4,20 @@ use std::sync::Arc;
 
 use edr_eth::remote::jsonrpc;
 use edr_provider::{time::CurrentTime, InvalidRequestReason};
-use napi::{tokio::runtime, Env, JsFunction, JsObject, Status};
+use napi::{bindgen_prelude::Buffer, tokio::runtime, Env, JsFunction, JsObject};
 use napi_derive::napi;
+use sha3::{Digest, Keccak256};
 
 use self::config::ProviderConfig;
 use crate::{
     call_override::CallOverrideCallback,
     context::EdrContext,
     logger::{Logger, LoggerConfig, LoggerError},
+    result::{
+        structured_error, structured_error_data, ExecutionResult, ProviderErrorCode,
+        TransactionFailedData,
+    },
+    step_callback::StepCallback,
     subscribe::SubscriberCallback,
     trace::RawTrace,
 };
@@ -61,7 +67,13 @@ impl Provider {
                 CurrentTime,
             )
             .map_or_else(
-                |error| Err(napi::Error::new(Status::GenericFailure, error.to_string())),
+                |error| {
+                    Err(structured_error::<()>(
+                        ProviderErrorCode::Internal,
+                        error.to_string(),
+                        None,
+                    ))
+                },
                 |provider| {
                     Ok(Provider {
                         provider: Arc::new(provider),
@@ -100,7 +112,11 @@ impl Provider {
                         })
                         .await
                         .map_err(|error| {
-                            napi::Error::new(Status::GenericFailure, error.to_string())
+                            structured_error::<()>(
+                                ProviderErrorCode::Internal,
+                                error.to_string(),
+                                None,
+                            )
                         })?;
                 }
 
@@ -115,13 +131,15 @@ impl Provider {
 
                 return serde_json::to_string(&response)
                     .map_err(|error| {
-                        napi::Error::new(
-                            Status::InvalidArg,
+                        structured_error::<()>(
+                            ProviderErrorCode::InvalidRequest,
                             format!("Invalid JSON `{json_request}` due to: {error}"),
+                            None,
                         )
                     })
                     .map(|json_response| Response {
                         solidity_trace: None,
+                        execution_result: None,
                         json: json_response,
                         traces: Vec::new(),
                     });
@@ -133,28 +151,57 @@ impl Provider {
             crate::scenarios::write_request(scenario_file, &request).await?;
         }
 
+        // `eth_sendRawTransaction`'s param is the transaction's raw signed
+        // encoding, so its hash is just `keccak256` of those bytes -- computable
+        // here without needing anything `edr_provider::TransactionFailed` does,
+        // or doesn't, carry. Other submission methods (`eth_sendTransaction`,
+        // `eth_call`) don't hand over raw bytes we can hash ourselves, so they
+        // stay `None`; `execution_result`'s origins are not available to this
+        // call site.
+        let raw_transaction_hash = serde_json::from_str::<serde_json::Value>(&json_request)
+            .ok()
+            .filter(|value| {
+                value.get("method").and_then(serde_json::Value::as_str)
+                    == Some("eth_sendRawTransaction")
+            })
+            .and_then(|value| {
+                value.get("params")?.get(0)?.as_str().map(str::to_string)
+            })
+            .and_then(|raw| edr_evm::hex::decode(raw.trim_start_matches("0x")).ok())
+            .map(|raw| Buffer::from(Keccak256::digest(&raw).as_slice()));
+
         let mut response = runtime::Handle::current()
             .spawn_blocking(move || provider.handle_request(request))
             .await
-            .map_err(|e| napi::Error::new(Status::GenericFailure, e.to_string()))?;
-
-        // We can take the solidity trace as it won't be used for anything else
-        let solidity_trace = response.as_mut().err().and_then(|error| {
-            if let edr_provider::ProviderError::TransactionFailed(failure) = error {
-                if matches!(
-                    failure.failure.reason,
-                    edr_provider::TransactionFailureReason::OutOfGas(_)
-                ) {
-                    None
+            .map_err(|error| {
+                structured_error::<()>(ProviderErrorCode::Internal, error.to_string(), None)
+            })?;
+
+        // We can take the solidity trace as it won't be used for anything else. A
+        // bare `OutOfGas` is an artifact of the `eth_estimateGas` binary search
+        // probing a gas limit that was too low, not the genuine revert/halt that
+        // caused the search to fail at its upper bound, so neither the trace nor
+        // the execution result are surfaced for it.
+        let (solidity_trace, execution_result) = response.as_mut().err().map_or(
+            (None, None),
+            |error| {
+                if let edr_provider::ProviderError::TransactionFailed(failure) = error {
+                    if matches!(
+                        failure.failure.reason,
+                        edr_provider::TransactionFailureReason::OutOfGas(_)
+                    ) {
+                        (None, None)
+                    } else {
+                        let solidity_trace = std::mem::take(&mut failure.failure.solidity_trace);
+                        let execution_result = last_after_message(&solidity_trace);
+
+                        (Some(Arc::new(solidity_trace)), execution_result)
+                    }
                 } else {
-                    Some(Arc::new(std::mem::take(
-                        &mut failure.failure.solidity_trace,
-                    )))
+                    (None, None)
                 }
-            } else {
-                None
-            }
-        });
+            },
+        );
 
         // We can take the traces as they won't be used for anything else
         let traces = match &mut response {
@@ -165,12 +212,42 @@ impl Provider {
             Err(_) => Vec::new(),
         };
 
-        let response = jsonrpc::ResponseData::from(response.map(|response| response.result));
+        // `execution_result` is only populated for a genuine (non-`OutOfGas`)
+        // `TransactionFailed`, so its presence also tells us whether to attach
+        // structured failure data to the JSON-RPC error below.
+        let transaction_failed_data = execution_result.as_ref().map(|message| {
+            let return_data = match &message.execution_result {
+                edr_evm::ExecutionResult::Revert { output, .. } => {
+                    Some(Buffer::from(output.as_ref()))
+                }
+                _ => None,
+            };
+
+            TransactionFailedData {
+                transaction_hash: raw_transaction_hash,
+                return_data,
+            }
+        });
+
+        let mut response = jsonrpc::ResponseData::from(response.map(|response| response.result));
+
+        if let (Some(data), jsonrpc::ResponseData::Error { error }) =
+            (&transaction_failed_data, &mut response)
+        {
+            error.data = Some(structured_error_data(
+                ProviderErrorCode::TransactionFailed,
+                error.message.clone(),
+                Some(data),
+            ));
+        }
 
         serde_json::to_string(&response)
-            .map_err(|e| napi::Error::new(Status::GenericFailure, e.to_string()))
+            .map_err(|error| {
+                structured_error::<()>(ProviderErrorCode::Serialization, error.to_string(), None)
+            })
             .map(|json_response| Response {
                 solidity_trace,
+                execution_result,
                 json: json_response,
                 traces: traces.into_iter().map(Arc::new).collect(),
             })
@@ -197,22 +274,99 @@ impl Provider {
         Ok(())
     }
 
-    /// Set to `true` to make the traces returned with `eth_call`,
-    /// `eth_estimateGas`, `eth_sendRawTransaction`, `eth_sendTransaction`,
-    /// `evm_mine`, `hardhat_mine` include the full stack and memory. Set to
-    /// `false` to disable this.
+    /// Sets the capture configuration for the traces returned with
+    /// `eth_call`, `eth_estimateGas`, `eth_sendRawTransaction`,
+    /// `eth_sendTransaction`, `evm_mine`, `hardhat_mine`. Lets callers that
+    /// only need (e.g.) the call tree or gas usage opt out of marshalling
+    /// full stack and memory words for every step across N-API.
     #[napi(ts_return_type = "void")]
-    pub fn set_verbose_tracing(&self, verbose_tracing: bool) {
-        self.provider.set_verbose_tracing(verbose_tracing);
+    pub fn set_trace_config(&self, trace_config: TraceConfig) -> napi::Result<()> {
+        self.provider.set_trace_config(trace_config.try_into()?);
+
+        Ok(())
+    }
+
+    /// Sets a callback that's invoked for every opcode executed by the EVM,
+    /// across all transactions run by this provider, as they happen. Unlike
+    /// `Response.traces`, this doesn't wait for the request to finish, which
+    /// lets Hardhat-side tooling (gas-usage heatmaps, custom assertion
+    /// hooks, coverage) observe execution directly rather than
+    /// post-processing a completed trace.
+    #[napi(ts_return_type = "void")]
+    pub fn set_step_callback(
+        &self,
+        env: Env,
+        #[napi(ts_arg_type = "(step: TracingStep) => void")] step_callback: JsFunction,
+    ) -> napi::Result<()> {
+        let step_callback = StepCallback::new(&env, step_callback)?;
+        let step_callback = Arc::new(move |step| step_callback.call(step));
+
+        self.provider.set_step_callback(Some(step_callback));
+
+        Ok(())
     }
 }
 
+/// Per-trace capture configuration for traces returned via
+/// [`Provider::set_trace_config`]. Supersedes the coarser all-or-nothing
+/// verbose-tracing boolean this replaced.
+#[napi(object)]
+pub struct TraceConfig {
+    /// Include the full memory array in each captured step. Defaults to
+    /// `false`.
+    pub enable_memory: Option<bool>,
+    /// Include the full stack in each captured step, rather than just its
+    /// top element. Defaults to `false`.
+    pub enable_stack: Option<bool>,
+    /// Include the storage diff in each captured step. Defaults to
+    /// `false`.
+    pub enable_storage: Option<bool>,
+    /// Caps the number of steps captured per trace. Unlimited if not
+    /// provided.
+    pub struct_log_limit: Option<u32>,
+}
+
+impl TryFrom<TraceConfig> for edr_provider::TraceConfig {
+    type Error = napi::Error;
+
+    fn try_from(value: TraceConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            enable_memory: value.enable_memory.unwrap_or(false),
+            enable_stack: value.enable_stack.unwrap_or(false),
+            enable_storage: value.enable_storage.unwrap_or(false),
+            struct_log_limit: value
+                .struct_log_limit
+                .map(|limit| usize::try_from(limit))
+                .transpose()
+                .map_err(|error: std::num::TryFromIntError| {
+                    napi::Error::new(napi::Status::InvalidArg, error.to_string())
+                })?,
+        })
+    }
+}
+
+/// Finds the last `After` message in a trace, i.e. the result of the
+/// outermost call. Used to surface *why* a transaction failed (the decoded
+/// revert reason or exceptional halt) rather than just that it did.
+fn last_after_message(trace: &edr_evm::trace::Trace) -> Option<edr_evm::trace::AfterMessage> {
+    trace.messages.iter().rev().find_map(|message| match message {
+        edr_evm::trace::TraceMessage::After(message) => Some(message.clone()),
+        _ => None,
+    })
+}
+
 #[napi]
 pub struct Response {
     json: String,
     /// When a transaction fails to execute, the provider returns a trace of the
     /// transaction.
     solidity_trace: Option<Arc<edr_evm::trace::Trace>>,
+    /// When a transaction fails with a genuine revert or exceptional halt
+    /// (but not merely because `eth_estimateGas`'s binary search probed too
+    /// low a gas limit), this carries that outcome so JS callers can show
+    /// why the transaction cannot succeed at any gas, rather than a generic
+    /// error.
+    execution_result: Option<edr_evm::trace::AfterMessage>,
     /// This may contain zero or more traces, depending on the (batch) request
     traces: Vec<Arc<edr_evm::trace::Trace>>,
 }
@@ -231,6 +385,14 @@ impl Response {
             .map(|trace| RawTrace::new(trace.clone()))
     }
 
+    #[napi(getter)]
+    pub fn execution_result(&self, env: Env) -> napi::Result<Option<ExecutionResult>> {
+        self.execution_result
+            .as_ref()
+            .map(|message| ExecutionResult::new(&env, message))
+            .transpose()
+    }
+
     #[napi(getter)]
     pub fn traces(&self) -> Vec<RawTrace> {
         self.traces
@@ -1,12 +1,125 @@
 use edr_evm::trace::AfterMessage;
 use napi::{
     bindgen_prelude::{BigInt, Buffer, Either3},
-    Either, Env, JsBuffer, JsBufferValue,
+    Either, Env, JsBuffer, JsBufferValue, Status,
 };
 use napi_derive::napi;
 
 use crate::log::ExecutionLog;
 
+/// A stable, machine-readable classification of a failure that crossed the
+/// NAPI boundary, so JS consumers can branch on `error.code` instead of
+/// pattern-matching the human-readable message.
+#[napi(string_enum)]
+pub enum ProviderErrorCode {
+    /// The JSON-RPC request couldn't be parsed or failed input validation.
+    InvalidRequest,
+    /// A transaction reverted or halted during execution.
+    TransactionFailed,
+    /// An argument supplied to the provider was invalid.
+    InvalidArgument,
+    /// Serializing the response to/from JSON failed.
+    Serialization,
+    /// An unexpected, internal failure (e.g. a panicked worker thread).
+    Internal,
+}
+
+/// The structured payload attached to a [`ProviderErrorCode::TransactionFailed`]
+/// error, letting consumers inspect why a transaction failed without
+/// re-parsing the message.
+#[napi(object)]
+pub struct TransactionFailedData {
+    /// The hash of the failed transaction. Only populated for
+    /// `eth_sendRawTransaction`, whose raw signed encoding lets the hash be
+    /// computed directly; other submission methods (`eth_sendTransaction`,
+    /// `eth_call`) don't hand this binding layer anything to hash it from.
+    pub transaction_hash: Option<Buffer>,
+    /// The raw revert/return data of the failed transaction, if any.
+    pub return_data: Option<Buffer>,
+}
+
+impl serde::Serialize for TransactionFailedData {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        struct Repr {
+            transaction_hash: Option<String>,
+            return_data: Option<String>,
+        }
+
+        let to_hex = |buffer: &Buffer| format!("0x{}", edr_evm::hex::encode(buffer.as_ref()));
+
+        Repr {
+            transaction_hash: self.transaction_hash.as_ref().map(to_hex),
+            return_data: self.return_data.as_ref().map(to_hex),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(serde::Serialize)]
+struct StructuredError<D> {
+    code: &'static str,
+    message: String,
+    data: Option<D>,
+}
+
+fn code_str(code: ProviderErrorCode) -> &'static str {
+    match code {
+        ProviderErrorCode::InvalidRequest => "InvalidRequest",
+        ProviderErrorCode::TransactionFailed => "TransactionFailed",
+        ProviderErrorCode::InvalidArgument => "InvalidArgument",
+        ProviderErrorCode::Serialization => "Serialization",
+        ProviderErrorCode::Internal => "Internal",
+    }
+}
+
+/// Builds a [`napi::Error`] carrying a stable `code`, a human `message`, and
+/// an optional structured `data` payload, serialized as a JSON object so JS
+/// consumers can `JSON.parse(error.message)` instead of string-matching.
+///
+/// This centralizes what would otherwise be scattered
+/// `napi::Error::new(Status::GenericFailure, err.to_string())` call sites.
+pub fn structured_error<D: serde::Serialize>(
+    code: ProviderErrorCode,
+    message: impl Into<String>,
+    data: Option<D>,
+) -> napi::Error {
+    let code_str = code_str(code);
+    let payload = StructuredError {
+        code: code_str,
+        message: message.into(),
+        data,
+    };
+
+    let reason = serde_json::to_string(&payload)
+        .unwrap_or_else(|_| format!(r#"{{"code":"{code_str}","message":"failed to serialize error"}}"#));
+
+    napi::Error::new(Status::GenericFailure, reason)
+}
+
+/// Builds the same `{code, message, data}` envelope as [`structured_error`],
+/// but as a plain [`serde_json::Value`] rather than a thrown [`napi::Error`].
+///
+/// Some failures (e.g. a reverted transaction) are surfaced as an ordinary
+/// JSON-RPC error response rather than an exception thrown across the NAPI
+/// boundary, since callers still need the `Response` object (e.g. for
+/// `Response::execution_result`/`Response::solidity_trace`). This lets call
+/// sites attach the same structured `data` to that JSON-RPC error's `data`
+/// field instead.
+pub fn structured_error_data<D: serde::Serialize>(
+    code: ProviderErrorCode,
+    message: impl Into<String>,
+    data: Option<D>,
+) -> serde_json::Value {
+    let payload = StructuredError {
+        code: code_str(code),
+        message: message.into(),
+        data,
+    };
+
+    serde_json::to_value(payload).unwrap_or(serde_json::Value::Null)
+}
+
 /// The possible reasons for successful termination of the EVM.
 #[napi]
 pub enum SuccessReason {
@@ -97,6 +210,16 @@ pub enum ExceptionalHalt {
     CreateContractStartingWithEF,
     /// EIP-3860: Limit and meter initcode. Initcode size limit exceeded.
     CreateInitCodeSizeLimit,
+    /// EIP-7069: An `EXTCALL`/`EXTDELEGATECALL`/`EXTSTATICCALL` target stack
+    /// word had non-zero high-order bytes, i.e. it wasn't a valid
+    /// left-padded 20-byte address. The call's gas is consumed regardless.
+    InvalidEXTCALLTarget,
+    /// EIP-4750: a `CALLF` pushed the EOF function call stack past its
+    /// 1024-frame limit.
+    EofFunctionStackOverflow,
+    /// EIP-4750: a `RETF` was executed with an empty EOF function call
+    /// stack.
+    EofFunctionStackUnderflow,
 }
 
 impl From<edr_evm::HaltReason> for ExceptionalHalt {
@@ -122,6 +245,13 @@ impl From<edr_evm::HaltReason> for ExceptionalHalt {
             edr_evm::HaltReason::CreateInitCodeSizeLimit => {
                 ExceptionalHalt::CreateInitCodeSizeLimit
             }
+            edr_evm::HaltReason::InvalidEXTCALLTarget => ExceptionalHalt::InvalidEXTCALLTarget,
+            edr_evm::HaltReason::EofFunctionStackOverflow => {
+                ExceptionalHalt::EofFunctionStackOverflow
+            }
+            edr_evm::HaltReason::EofFunctionStackUnderflow => {
+                ExceptionalHalt::EofFunctionStackUnderflow
+            }
             edr_evm::HaltReason::OverflowPayment
             | edr_evm::HaltReason::StateChangeDuringStaticCall
             | edr_evm::HaltReason::CallNotAllowedInsideStatic
@@ -150,6 +280,9 @@ impl From<ExceptionalHalt> for edr_evm::HaltReason {
             ExceptionalHalt::CreateContractSizeLimit => Self::CreateContractSizeLimit,
             ExceptionalHalt::CreateContractStartingWithEF => Self::CreateContractStartingWithEF,
             ExceptionalHalt::CreateInitCodeSizeLimit => Self::CreateInitCodeSizeLimit,
+            ExceptionalHalt::InvalidEXTCALLTarget => Self::InvalidEXTCALLTarget,
+            ExceptionalHalt::EofFunctionStackOverflow => Self::EofFunctionStackOverflow,
+            ExceptionalHalt::EofFunctionStackUnderflow => Self::EofFunctionStackUnderflow,
         }
     }
 }
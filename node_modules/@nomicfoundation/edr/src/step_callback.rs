@@ -0,0 +1,41 @@
+use napi::{
+    threadsafe_function::{
+        ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+    },
+    Env, JsFunction,
+};
+
+use crate::trace::TracingStep;
+
+/// A callback into JS invoked for every opcode the EVM inspector executes,
+/// mirroring [`crate::call_override::CallOverrideCallback`]'s shape. Lets
+/// Hardhat-side tooling observe execution step-by-step (gas-usage heatmaps,
+/// coverage, custom assertion hooks) without waiting for a full
+/// [`crate::trace::RawTrace`].
+#[derive(Clone)]
+pub struct StepCallback {
+    inner: ThreadsafeFunction<edr_evm::trace::Step, ErrorStrategy::Fatal>,
+}
+
+impl StepCallback {
+    pub fn new(env: &Env, step_callback: JsFunction) -> napi::Result<Self> {
+        let mut callback = step_callback.create_threadsafe_function(
+            0,
+            |ctx: ThreadSafeCallContext<edr_evm::trace::Step>| {
+                Ok(vec![TracingStep::new(&ctx.value)])
+            },
+        )?;
+
+        // Maintain a weak reference to the function to avoid the event loop from
+        // exiting.
+        callback.unref(env)?;
+
+        Ok(Self { inner: callback })
+    }
+
+    /// Invokes the callback for a single step. Blocking to guarantee steps
+    /// are delivered to JS in the order they were executed.
+    pub fn call(&self, step: edr_evm::trace::Step) {
+        self.inner.call(step, ThreadsafeFunctionCallMode::Blocking);
+    }
+}
@@ -1,6 +1,9 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use edr_evm::{interpreter::OpCode, trace::BeforeMessage};
+use edr_evm::{
+    interpreter::OpCode,
+    trace::{AfterMessage, BeforeMessage},
+};
 use napi::{
     bindgen_prelude::{BigInt, Buffer, Either3},
     Env, JsBuffer, JsBufferValue,
@@ -110,6 +113,22 @@ pub struct TracingStep {
     /// The memory at the step. None if verbose tracing is disabled.
     #[napi(readonly)]
     pub memory: Option<Buffer>,
+    /// Remaining gas before executing this op.
+    #[napi(readonly)]
+    pub gas: BigInt,
+    /// Cost of this op, including any dynamic component.
+    #[napi(readonly)]
+    pub gas_cost: BigInt,
+    /// Accumulated gas refund counter after this op.
+    #[napi(readonly)]
+    pub refund: BigInt,
+    /// Storage slots touched by this op, keyed by hex-encoded slot to
+    /// hex-encoded value. None if verbose tracing is disabled.
+    #[napi(readonly)]
+    pub storage: Option<HashMap<String, String>>,
+    /// A description of the error that occurred during this op, if any.
+    #[napi(readonly)]
+    pub error: Option<String>,
 }
 
 impl TracingStep {
@@ -124,6 +143,12 @@ impl TracingStep {
             |stack| stack.iter().map(u256_to_bigint).collect(),
         );
         let memory = step.memory.as_ref().cloned().map(Buffer::from);
+        let storage = step.storage.as_ref().map(|storage| {
+            storage
+                .iter()
+                .map(|(key, value)| (format!("{key:#x}"), format!("{value:#x}")))
+                .collect()
+        });
 
         Self {
             depth: step.depth as u8,
@@ -131,6 +156,11 @@ impl TracingStep {
             opcode: OpCode::name_by_op(step.opcode).to_string(),
             stack,
             memory,
+            gas: BigInt::from(step.gas),
+            gas_cost: BigInt::from(step.gas_cost),
+            refund: BigInt::from(step.refund),
+            storage,
+            error: step.error.clone(),
         }
     }
 }
@@ -180,4 +210,170 @@ impl RawTrace {
             })
             .collect::<napi::Result<_>>()
     }
+
+    /// Folds the flat `messages` stream into a nested call tree mirroring
+    /// geth's `callTracer`. `Step` messages are skipped.
+    #[napi]
+    pub fn call_tree(&self) -> Vec<CallTreeNode> {
+        fold_call_tree(&self.inner.messages)
+    }
+}
+
+/// Folds a flat `TraceMessage` stream into a nested call tree. Shared by
+/// [`RawTrace::call_tree`] and the `callTracer` built-in tracer in
+/// `debug_trace`.
+pub(crate) fn fold_call_tree(messages: &[edr_evm::trace::TraceMessage]) -> Vec<CallTreeNode> {
+    // Frames still awaiting their matching `After` message, deepest last.
+    let mut open: Vec<CallTreeNode> = Vec::new();
+    let mut roots: Vec<CallTreeNode> = Vec::new();
+
+    for message in messages {
+        match message {
+            edr_evm::trace::TraceMessage::Before(message) => {
+                open.push(CallTreeNode::new(message));
+            }
+            edr_evm::trace::TraceMessage::Step(_) => {}
+            edr_evm::trace::TraceMessage::After(message) => {
+                if let Some(mut node) = open.pop() {
+                    node.fill_result(message);
+
+                    match open.last_mut() {
+                        Some(parent) => parent.calls.push(node),
+                        None => roots.push(node),
+                    }
+                }
+            }
+        }
+    }
+
+    // The trace can terminate mid-depth, e.g. an out-of-gas unwind that
+    // never emits the matching `After` messages for its ancestors. Flush
+    // whatever frames are still open, deepest-first, leaving their
+    // result fields at the `CallTreeNode::new` defaults.
+    while let Some(node) = open.pop() {
+        match open.last_mut() {
+            Some(parent) => parent.calls.push(node),
+            None => roots.push(node),
+        }
+    }
+
+    roots
+}
+
+/// A single node of a call tree, as returned by [`RawTrace::call_tree`].
+/// Mirrors the shape of geth's `callTracer` output.
+#[napi(object)]
+#[derive(Clone)]
+pub struct CallTreeNode {
+    /// `CALL`, `STATICCALL`, `DELEGATECALL`, or `CREATE`.
+    ///
+    /// `CREATE2` and `CALLCODE` can't be told apart from `CREATE`/
+    /// `DELEGATECALL` respectively with the fields available on
+    /// [`BeforeMessage`] (no salt or call-opcode is carried), so they're
+    /// reported under their more common sibling.
+    #[napi(js_name = "type")]
+    pub call_type: String,
+    pub from: Buffer,
+    pub to: Option<Buffer>,
+    pub value: BigInt,
+    pub gas: BigInt,
+    pub gas_used: BigInt,
+    pub input: Buffer,
+    pub output: Option<Buffer>,
+    pub error: Option<String>,
+    pub revert_reason: Option<String>,
+    pub calls: Vec<CallTreeNode>,
+}
+
+impl CallTreeNode {
+    fn new(message: &BeforeMessage) -> Self {
+        let call_type = if message.to.is_none() {
+            "CREATE"
+        } else if message.is_static_call {
+            "STATICCALL"
+        } else if message
+            .code_address
+            .is_some_and(|code_address| Some(code_address) != message.to)
+        {
+            "DELEGATECALL"
+        } else {
+            "CALL"
+        };
+
+        Self {
+            call_type: call_type.to_string(),
+            from: Buffer::from(message.caller.as_slice()),
+            to: message.to.map(|to| Buffer::from(to.as_slice())),
+            value: u256_to_bigint(&message.value),
+            gas: BigInt::from(message.gas_limit),
+            gas_used: BigInt::from(0u64),
+            input: Buffer::from(message.data.as_ref()),
+            output: None,
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+        }
+    }
+
+    fn fill_result(&mut self, message: &AfterMessage) {
+        match &message.execution_result {
+            edr_evm::ExecutionResult::Success { gas_used, .. } => {
+                self.gas_used = BigInt::from(*gas_used);
+            }
+            edr_evm::ExecutionResult::Revert { gas_used, output } => {
+                self.gas_used = BigInt::from(*gas_used);
+                self.error = Some("execution reverted".to_string());
+                self.revert_reason = decode_known_revert_reason(output);
+                self.output = Some(Buffer::from(output.as_ref()));
+            }
+            edr_evm::ExecutionResult::Halt { reason, gas_used } => {
+                self.gas_used = BigInt::from(*gas_used);
+                self.error = Some(halt_reason_str(reason).to_string());
+            }
+        }
+    }
+}
+
+// The standard `Error(string)` revert selector.
+// See <https://docs.soliditylang.org/en/v0.8.26/control-structures.html#error-handling-assert-require-revert-and-exceptions>
+alloy_sol_types::sol! {
+    error Error(string);
+}
+
+/// Best-effort decoding of the standard `Error(string)` revert reason, for
+/// [`CallTreeNode::revert_reason`]. `None` for custom errors, `Panic`, or
+/// malformed payloads.
+pub(crate) fn decode_known_revert_reason(output: &[u8]) -> Option<String> {
+    use alloy_sol_types::SolError;
+
+    Error::abi_decode(output, false).ok().map(|error| error._0)
+}
+
+pub(crate) fn halt_reason_str(reason: &edr_evm::HaltReason) -> &'static str {
+    match reason {
+        edr_evm::HaltReason::OutOfGas(..) => "out of gas",
+        edr_evm::HaltReason::OpcodeNotFound => "invalid opcode",
+        edr_evm::HaltReason::InvalidFEOpcode => "invalid opcode",
+        edr_evm::HaltReason::InvalidJump => "invalid jump destination",
+        edr_evm::HaltReason::NotActivated => "not activated",
+        edr_evm::HaltReason::StackUnderflow => "stack underflow",
+        edr_evm::HaltReason::StackOverflow => "stack overflow",
+        edr_evm::HaltReason::OutOfOffset => "out of offset",
+        edr_evm::HaltReason::CreateCollision => "contract address collision",
+        edr_evm::HaltReason::PrecompileError => "precompile error",
+        edr_evm::HaltReason::NonceOverflow => "nonce overflow",
+        edr_evm::HaltReason::CreateContractSizeLimit => "contract size limit exceeded",
+        edr_evm::HaltReason::CreateContractStartingWithEF => {
+            "invalid contract prefix (EIP-3541)"
+        }
+        edr_evm::HaltReason::CreateInitCodeSizeLimit => "init code size limit exceeded",
+        edr_evm::HaltReason::InvalidEXTCALLTarget => "invalid EXTCALL target",
+        edr_evm::HaltReason::EofFunctionStackOverflow => "EOF function stack overflow",
+        edr_evm::HaltReason::EofFunctionStackUnderflow => "EOF function stack underflow",
+        edr_evm::HaltReason::OverflowPayment
+        | edr_evm::HaltReason::StateChangeDuringStaticCall
+        | edr_evm::HaltReason::CallNotAllowedInsideStatic
+        | edr_evm::HaltReason::OutOfFunds
+        | edr_evm::HaltReason::CallTooDeep => "internal halt",
+    }
 }
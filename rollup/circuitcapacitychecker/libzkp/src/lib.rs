@@ -10,9 +10,11 @@ pub mod checker {
     };
     use serde_derive::{Deserialize, Serialize};
     use std::cell::OnceCell;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, VecDeque};
     use std::panic;
     use std::ptr::null;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Mutex, RwLock};
 
     #[derive(Debug, Clone, Deserialize, Serialize)]
     pub struct CommonResult {
@@ -31,40 +33,159 @@ pub mod checker {
         pub error: Option<String>,
     }
 
-    static mut CHECKERS: OnceCell<HashMap<u64, CircuitCapacityChecker>> = OnceCell::new();
+    #[derive(Debug, Clone, Deserialize, Serialize)]
+    pub struct EstimateTxResult {
+        pub acc_row_usage: Option<RowUsage>,
+        /// Whether applying this tx on top of the checker's current
+        /// accumulated usage would exceed any sub-circuit's configured row
+        /// capacity.
+        pub overflow: bool,
+        /// The name of the sub-circuit that is closest to (or over) its row
+        /// capacity, for logging why a block would be closed.
+        pub limiting_circuit: Option<String>,
+        pub error: Option<String>,
+    }
+
+    /// Each live [`CircuitCapacityChecker`] is wrapped in its own `Mutex`, so
+    /// concurrent calls against different ids only contend on the outer
+    /// `RwLock` (held briefly, read-side, to look up the per-checker lock)
+    /// rather than serializing on a single global lock.
+    static mut CHECKERS: OnceCell<RwLock<HashMap<u64, Mutex<CircuitCapacityChecker>>>> =
+        OnceCell::new();
+    /// Monotonic id source for [`new_circuit_capacity_checker`]. Ids are never
+    /// reused, so a deleted checker's id can't collide with a later one.
+    static mut NEXT_CHECKER_ID: OnceCell<AtomicU64> = OnceCell::new();
+    /// Ids ordered from least- to most-recently-used, maintained by
+    /// [`touch_checker`]. The front is evicted first when the registry is at
+    /// capacity.
+    static mut CHECKER_RECENCY: OnceCell<Mutex<VecDeque<u64>>> = OnceCell::new();
+    /// The configured maximum number of live checkers, set at [`init`]. `0`
+    /// means unbounded.
+    static mut MAX_CHECKERS: OnceCell<u64> = OnceCell::new();
+
+    unsafe fn checkers() -> &'static RwLock<HashMap<u64, Mutex<CircuitCapacityChecker>>> {
+        CHECKERS
+            .get()
+            .expect("circuit capacity checkers map not initialized")
+    }
+
+    unsafe fn checker_recency() -> &'static Mutex<VecDeque<u64>> {
+        CHECKER_RECENCY
+            .get()
+            .expect("circuit capacity checker recency list not initialized")
+    }
+
+    /// Marks `id` as the most-recently-used checker. A no-op for an `id` that
+    /// isn't (or is no longer) in `checkers()`, so a stale id can't get
+    /// inserted into the recency list and later evict a live checker without
+    /// anything to actually remove.
+    unsafe fn touch_checker(id: u64) {
+        if !checkers()
+            .read()
+            .expect("fail to lock circuit capacity checkers map in touch_checker")
+            .contains_key(&id)
+        {
+            return;
+        }
+
+        let mut recency = checker_recency()
+            .lock()
+            .expect("fail to lock circuit capacity checker recency list in touch_checker");
+        recency.retain(|existing_id| *existing_id != id);
+        recency.push_back(id);
+    }
 
     /// # Safety
     #[no_mangle]
-    pub unsafe extern "C" fn init() {
+    pub unsafe extern "C" fn init(max_checkers: u64) {
         env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
             .format_timestamp_millis()
             .init();
-        let checkers = HashMap::new();
         CHECKERS
-            .set(checkers)
+            .set(RwLock::new(HashMap::new()))
+            .expect("circuit capacity checker initialized twice");
+        NEXT_CHECKER_ID
+            .set(AtomicU64::new(0))
+            .expect("circuit capacity checker initialized twice");
+        CHECKER_RECENCY
+            .set(Mutex::new(VecDeque::new()))
+            .expect("circuit capacity checker initialized twice");
+        MAX_CHECKERS
+            .set(max_checkers)
             .expect("circuit capacity checker initialized twice");
     }
 
+    /// Allocates a new checker, evicting the least-recently-used one first if
+    /// the registry is already at the capacity configured via [`init`].
+    ///
+    /// If non-null, `evicted_id_out` is set to the id of the evicted checker,
+    /// or to `-1` if none was evicted.
+    ///
     /// # Safety
     #[no_mangle]
-    pub unsafe extern "C" fn new_circuit_capacity_checker() -> u64 {
-        let checkers = CHECKERS
-            .get_mut()
-            .expect("fail to get circuit capacity checkers map in new_circuit_capacity_checker");
-        let id = checkers.len() as u64;
+    pub unsafe extern "C" fn new_circuit_capacity_checker(evicted_id_out: *mut i64) -> u64 {
+        let max_checkers = *MAX_CHECKERS
+            .get()
+            .expect("circuit capacity checker max not initialized");
+
+        let mut evicted_id: i64 = -1;
+        if max_checkers > 0 {
+            let mut checkers = checkers()
+                .write()
+                .expect("fail to lock circuit capacity checkers map in new_circuit_capacity_checker");
+            if checkers.len() as u64 >= max_checkers {
+                let mut recency = checker_recency().lock().expect(
+                    "fail to lock circuit capacity checker recency list in new_circuit_capacity_checker",
+                );
+                if let Some(lru_id) = recency.pop_front() {
+                    checkers.remove(&lru_id);
+                    evicted_id = lru_id as i64;
+                }
+            }
+        }
+
+        if !evicted_id_out.is_null() {
+            *evicted_id_out = evicted_id;
+        }
+
+        let id = NEXT_CHECKER_ID
+            .get()
+            .expect("circuit capacity checker id counter not initialized")
+            .fetch_add(1, Ordering::SeqCst);
         let checker = CircuitCapacityChecker::new();
-        checkers.insert(id, checker);
+        checkers()
+            .write()
+            .expect("fail to lock circuit capacity checkers map in new_circuit_capacity_checker")
+            .insert(id, Mutex::new(checker));
+        touch_checker(id);
         id
     }
 
+    /// Removes and drops the checker with the given id, if it exists.
+    ///
+    /// # Safety
+    #[no_mangle]
+    pub unsafe extern "C" fn delete_circuit_capacity_checker(id: u64) {
+        checkers()
+            .write()
+            .expect("fail to lock circuit capacity checkers map in delete_circuit_capacity_checker")
+            .remove(&id);
+        checker_recency()
+            .lock()
+            .expect("fail to lock circuit capacity checker recency list in delete_circuit_capacity_checker")
+            .retain(|existing_id| *existing_id != id);
+    }
+
     /// # Safety
     #[no_mangle]
     pub unsafe extern "C" fn reset_circuit_capacity_checker(id: u64) {
-        CHECKERS
-            .get_mut()
-            .expect("fail to get circuit capacity checkers map in reset_circuit_capacity_checker")
-            .get_mut(&id)
+        checkers()
+            .read()
+            .expect("fail to lock circuit capacity checkers map in reset_circuit_capacity_checker")
+            .get(&id)
             .unwrap_or_else(|| panic!("fail to get circuit capacity checker (id: {id:?}) in reset_circuit_capacity_checker"))
+            .lock()
+            .expect("fail to lock circuit capacity checker in reset_circuit_capacity_checker")
             .reset()
     }
 
@@ -111,16 +232,18 @@ pub mod checker {
             bail!("traces.tx_storage_trace.len() != 1");
         }
 
+        touch_checker(id);
+
         let r = panic::catch_unwind(|| {
-            CHECKERS
-                .get_mut()
-                .ok_or(anyhow!(
-                    "fail to get circuit capacity checkers map in apply_tx"
-                ))?
-                .get_mut(&id)
+            checkers()
+                .read()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checkers map in apply_tx"))?
+                .get(&id)
                 .ok_or(anyhow!(
                     "fail to get circuit capacity checker (id: {id:?}) in apply_tx"
                 ))?
+                .lock()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checker (id: {id:?}) in apply_tx"))?
                 .estimate_circuit_capacity(&[traces])
         });
         match r {
@@ -131,6 +254,102 @@ pub mod checker {
         }
     }
 
+    /// Checks whether a tx would still fit in the current block without
+    /// committing it: runs `estimate_circuit_capacity` against a cloned
+    /// snapshot of the checker's accumulated row usage, so the caller can
+    /// try-fit transactions while packing a block and only commit the chosen
+    /// one via [`apply_tx`].
+    ///
+    /// # Safety
+    #[no_mangle]
+    pub unsafe extern "C" fn estimate_tx(id: u64, tx_traces: *const c_char) -> *const c_char {
+        let result = estimate_tx_inner(id, tx_traces);
+        let r = match result {
+            Ok(acc_row_usage) => {
+                log::debug!(
+                    "id: {:?}, estimated acc_row_usage: {:?}",
+                    id,
+                    acc_row_usage.row_number,
+                );
+                // Rank by how close each sub-circuit is to *its own* row
+                // capacity, not by raw row count: a circuit with a much
+                // larger capacity can have the highest `row_number` while
+                // being nowhere near full, and would otherwise wrongly be
+                // blamed for closing the block.
+                let limiting_circuit = acc_row_usage
+                    .row_usage_details
+                    .iter()
+                    .max_by(|a, b| {
+                        let usage_ratio = |detail: &&_| {
+                            if detail.row_limit == 0 {
+                                0.0
+                            } else {
+                                detail.row_number as f64 / detail.row_limit as f64
+                            }
+                        };
+
+                        usage_ratio(a)
+                            .partial_cmp(&usage_ratio(b))
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .map(|detail| detail.name.clone());
+                EstimateTxResult {
+                    overflow: !acc_row_usage.is_ok,
+                    limiting_circuit,
+                    acc_row_usage: Some(acc_row_usage),
+                    error: None,
+                }
+            }
+            Err(e) => EstimateTxResult {
+                acc_row_usage: None,
+                overflow: false,
+                limiting_circuit: None,
+                error: Some(format!("{e:?}")),
+            },
+        };
+        serde_json::to_vec(&r).map_or(null(), vec_to_c_char)
+    }
+
+    unsafe fn estimate_tx_inner(id: u64, tx_traces: *const c_char) -> Result<RowUsage, Error> {
+        log::debug!(
+            "ccc estimate_tx raw input, id: {:?}, tx_traces: {:?}",
+            id,
+            c_char_to_str(tx_traces)?
+        );
+        let tx_traces_vec = c_char_to_vec(tx_traces);
+        let traces = serde_json::from_slice::<BlockTrace>(&tx_traces_vec)?;
+
+        if traces.transactions.len() != 1 {
+            bail!("traces.transactions.len() != 1");
+        }
+        if traces.execution_results.len() != 1 {
+            bail!("traces.execution_results.len() != 1");
+        }
+        if traces.tx_storage_trace.len() != 1 {
+            bail!("traces.tx_storage_trace.len() != 1");
+        }
+
+        let r = panic::catch_unwind(|| {
+            let mut snapshot = checkers()
+                .read()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checkers map in estimate_tx"))?
+                .get(&id)
+                .ok_or(anyhow!(
+                    "fail to get circuit capacity checker (id: {id:?}) in estimate_tx"
+                ))?
+                .lock()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checker (id: {id:?}) in estimate_tx"))?
+                .clone();
+            snapshot.estimate_circuit_capacity(&[traces])
+        });
+        match r {
+            Ok(result) => result,
+            Err(e) => {
+                bail!("estimate_circuit_capacity (id: {id:?}) error in estimate_tx, error: {e:?}")
+            }
+        }
+    }
+
     /// # Safety
     #[no_mangle]
     pub unsafe extern "C" fn apply_block(id: u64, block_trace: *const c_char) -> *const c_char {
@@ -164,16 +383,18 @@ pub mod checker {
         let block_trace = c_char_to_vec(block_trace);
         let traces = serde_json::from_slice::<BlockTrace>(&block_trace)?;
 
+        touch_checker(id);
+
         let r = panic::catch_unwind(|| {
-            CHECKERS
-                .get_mut()
-                .ok_or(anyhow!(
-                    "fail to get circuit capacity checkers map in apply_block"
-                ))?
-                .get_mut(&id)
+            checkers()
+                .read()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checkers map in apply_block"))?
+                .get(&id)
                 .ok_or(anyhow!(
                     "fail to get circuit capacity checker (id: {id:?}) in apply_block"
                 ))?
+                .lock()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checker (id: {id:?}) in apply_block"))?
                 .estimate_circuit_capacity(&[traces])
         });
         match r {
@@ -206,16 +427,17 @@ pub mod checker {
 
     unsafe fn get_tx_num_inner(id: u64) -> Result<u64, Error> {
         log::debug!("ccc get_tx_num raw input, id: {id}");
+        touch_checker(id);
         panic::catch_unwind(|| {
-            Ok(CHECKERS
-                .get_mut()
-                .ok_or(anyhow!(
-                    "fail to get circuit capacity checkers map in get_tx_num"
-                ))?
-                .get_mut(&id)
+            Ok(checkers()
+                .read()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checkers map in get_tx_num"))?
+                .get(&id)
                 .ok_or(anyhow!(
                     "fail to get circuit capacity checker (id: {id}) in get_tx_num"
                 ))?
+                .lock()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checker (id: {id}) in get_tx_num"))?
                 .get_tx_num() as u64)
         })
         .map_or_else(
@@ -240,15 +462,15 @@ pub mod checker {
     unsafe fn set_light_mode_inner(id: u64, light_mode: bool) -> Result<(), Error> {
         log::debug!("ccc set_light_mode raw input, id: {id}");
         panic::catch_unwind(|| {
-            CHECKERS
-                .get_mut()
-                .ok_or(anyhow!(
-                    "fail to get circuit capacity checkers map in set_light_mode"
-                ))?
-                .get_mut(&id)
+            checkers()
+                .read()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checkers map in set_light_mode"))?
+                .get(&id)
                 .ok_or(anyhow!(
                     "fail to get circuit capacity checker (id: {id}) in set_light_mode"
                 ))?
+                .lock()
+                .map_err(|_| anyhow!("fail to lock circuit capacity checker (id: {id}) in set_light_mode"))?
                 .set_light_mode(light_mode);
             Ok(())
         })